@@ -1,42 +1,125 @@
 //! The derive macro
 //!
 //! # Supported attributes:
+//!
+//! Attributes can be split across multiple `#[anno(...)]`, including ones added by `#[cfg_attr(..., anno(...))]`
+//!
+//! Values can be a string literal or a bare identifier, e.g. `#[anno(name = Foo)]` is the same as `#[anno(name = "Foo")]`
+//!
 //! ## structs
 //! #### on the type
 //! `#[anno(name = "name", exact)]`
 //!
 //! | attribute | description | required |
 //! | --- | --- | --- |
-//! |`name` | allows you to rename the type | no |
-//! | `exact` | marks the class as an `exact` class | no |
+//! |`name` | allows you to rename the type, `rename` is the same | no |
+//! | `name_template` | names the type from a template, each `{}` is replaced by the rust name, e.g. `Lua{}` is `LuaFoo`. `name` takes precedence | no |
+//! | `exact` | marks the class as an `exact` class, this cannot be combined with an index signature, e.g. from `flatten` | no |
+//! | `rename_all` | renames all of the fields, one of `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case` or `SCREAMING_SNAKE_CASE`. A field `name` takes precedence | no |
 //! | `guess` | tries to guess the type | no |
+//! | `bytes_as_string` | guesses `Vec<u8>`, `[u8]` and `[u8; N]` as `string` rather than `integer[]` | no |
+//! | `use_serde` | reads `#[serde(flatten)]` on the fields, a map is `flatten`ed into an index signature and any other type is `inline`d | no |
+//! | `self_refers_to_class` | a `self` type in the field and method types is the name of the class, e.g. `fun(self): self` is `fun(self): Vec2` | no |
+//! | `transparent` | emits an `@alias` to the type of the single field | no |
+//! | `with_location` | records the `file:line` of each field in `Field::location` | no |
+//! | `case_insensitive_names` | field names that only differ by case are duplicates, e.g. `fooBar` and `foobar` | no |
+//! | `opt_in` | fields are skipped unless they have a `lua_type` or `include` | no |
+//! | `allow_empty` | allows a class without any fields, otherwise this is an error | no |
+//! | `header` | a raw annotation written on the line before the `@class`, e.g. `"@diagnostic disable: missing-fields"`. Can be repeated | no |
+//! | `method("name", "type")` | adds a `@field` for a method after the data fields, e.g. `method("greet", "fun(self): string")`. Can be repeated | no |
+//! | `operator("name", "rhs", "result")` | adds an `@operator` after the `@class`, e.g. `operator("add", "Vec2", "Vec2")`. The unary `unm`, `len` and `bnot` only take a result, e.g. `operator("unm", "Vec2")`. Can be repeated | no |
+//! | `doc` | appended to the doc comments | no |
+//! | `doc_replace` | replaces the doc comments | no |
+//! | `since` | adds a `Since: version` line to the doc comments | no |
 //!
 //! ##### Notes about `#[anno(guess)]`
 //! This'll try to guess the types, defaulting to `any` if it cannot be sure.
 //!
 //! You can still use `#[anno(lua_type = "name")]` to override this behavior, per field.
 //!
-//! The guessing algorithm tries these types mappings://!
+//! The guessing algorithm tries these types mappings:
+//!
 //! | rust type | lua_type | note |
 //! |--- | --- | -- |
-//! | `String` | `"string"` | -- |
+//! | `String`, `str`, `char` | `"string"` | -- |
 //! | `bool` | `"boolean"` | -- |
 //! | `i8`, `i16`, `i32`, `i64`, `isize` | `"integer"` | -- |
 //! | `u8`, `u16`, `u32`, `u64`, `usize` | `"integer"` | -- |
+//! | `NonZeroU32`, `NonZero<u32>`, etc | `"integer"` | any of the `NonZero` integers |
 //! | `f32`, `f64` | `"number"` | -- |
 //! | -- | -- | -- |
 //! | `Option<T>` | `"T?"` | the `T` is one of these rust types  |
-//! | `Vec<T>` | `"T[]"` | the `T` is one of these rust types |
+//! | `Vec<T>`, `VecDeque<T>` | `"T[]"` | the `T` is one of these rust types |
+//! | `HashSet<T>`, `BTreeSet<T>` | `"T[]"` | the `T` is one of these rust types |
+//! | `[T]` | `"T[]"` | the `T` is one of these rust types |
+//! | `[T; N]` | `"T[]"` | the `T` is one of these rust types |
+//! | `(A, B)` | `"[A, B]"` | each element is one of these rust types |
+//! | `()` | `"nil"` | -- |
+//! | `&T`, `&mut T` | `"T"` | the reference is ignored |
+//! | `Box<T>`, `Rc<T>`, `Arc<T>`, `Cow<T>`, `Cell<T>`, `RefCell<T>` | `"T"` | the `T` is one of these rust types, e.g. `Arc<str>` is `"string"` |
+//! | `HashMap<K, V>`, `BTreeMap<K, V>` | `"table<K, V>"` | the `K` and `V` are one of these rust types |
+//! | `fn(A) -> R`, `dyn Fn(A) -> R`, `impl FnMut(A)`, etc | `"function"` | any of the `Fn` traits, e.g. `Box<dyn Fn()>` |
+//! | `dyn Trait`, `impl Trait` | `"any"` | any other trait |
+//! | `Self`, or the name of the type | the name of the class | e.g. `Option<Box<Node>>` is `"Node?"` |
+//! | a type parameter, e.g. `T` | `"T"` | -- |
+//! | any other type, e.g. `Direction` | `"Direction"` | only with `reference`, the rust name of another `Anno` type. It is checked against its `LUA_NAME`, so a renamed type needs a `lua_type` |
+//! | any other generic type, e.g. `Container<String>` | `"Container<string>"` | only with `reference`, the type arguments are one of these rust types |
 //! | -- | -- | -- |
-//! | -- | `"any"` | the default type if it cannot match |
+//! | -- | `"any"` | the default type if it cannot match, e.g. `Duration` or `PathBuf` |
+//!
+//! Paths are matched by their last segment, so `std::string::String` is the same as `String`
+//!
+//! Lifetimes are not part of the lua type, so `Borrowed<'a>` is a `Borrowed` class and `Container<'a, String>` is `"Container<string>"`. A class only lists its type parameters, e.g. `---@class Handle<T>`
+//!
+//! ##### Notes about `#[anno(transparent)]`
+//! This requires the struct to have exactly one field, named or not.
+//!
+//! The single field can use `#[anno(lua_type = "name")]`, otherwise its type will be guessed. It can also use `#[anno(reference)]`.
+//!
+//! ##### Notes about `#[anno(opt_in)]`
+//! Only the fields with a `lua_type` or `include` are used, the others are skipped as if they had `ignore`.
+//!
+//! An `include` field still needs a type, so use it with `guess` on the field or the type. `guess` never includes a field by itself.
+//!
+//! ##### Notes about tuple structs
+//! A tuple struct with a single field is the same as `#[anno(transparent)]`.
+//!
+//! With more fields it emits an `@alias` of a tuple of the field types, e.g. `struct Point(f64, f64);` is `---@alias Point [number, number]`.
+//! Each field can use `#[anno(lua_type = "name")]`, otherwise its type will be guessed. They can also use `#[anno(reference)]`.
+//!
+//! If any field has a `name` it is a class instead, e.g. `struct Point(#[anno(name = "x")] f64, #[anno(name = "y")] f64);` has the fields `x` and `y`.
+//! A field without a `name` is keyed by its position, e.g. `---@field [2] number`, and the types are still guessed.
+//! This key is not an index signature, so the class can still be `exact`
+//!
 //! #### on struct fields
 //! `#[anno(name = "name", lua_type = "type_name")]`
 //!
 //! | attribute | description | required |
 //! | --- | --- | --- |
-//! |`name` | allows you to rename the field | no |
-//! | `lua_type` | the lua type this type should appear as | yes if `guess` is not used |
-//! | `ignore` | skips this field entirely | no |
+//! |`name` | allows you to rename the field, `rename` is the same | no |
+//! | `lua_type` | the lua type this type should appear as, `{ty}` is replaced with the guessed type, e.g. `"{ty}?"` | yes if `guess` is not used |
+//! | `lua_type_parts("a", "b")` | the same as `lua_type`, but the parts are joined together, e.g. for a long `fun(...)` | no |
+//! | `ignore` | skips this field entirely, `skip` is the same | no |
+//! | `include` | uses this field when the type is `opt_in` | no |
+//! | `guess` | tries to guess the type of just this field | no |
+//! | `no_guess` | uses `any` instead of guessing the type of this field | no |
+//! | `optional_key` | marks the key as optional, e.g. `name? type` rather than `name type?` | no |
+//! | `flatten` | emits a `HashMap<K, V>` or `BTreeMap<K, V>` as an index signature, e.g. `[K] V`, or a `Vec<T>` or array as `[integer] T`. `lua_type` replaces the value type | no |
+//! | `inline` | uses the fields of this field's class in its place, with that class's own names and types. Its docs and `exact` are not used. A field that is already in the class is skipped, and reported by [`validate_all`] | no |
+//! | `reference` | the type (or the types inside of an `Option`, `Vec`, etc) implements `Anno`, see [`Registry`]. Its guessed type is its name rather than `any` | no |
+//! | `doc` | appended to the doc comments | no |
+//! | `doc_replace` | replaces the doc comments | no |
+//! | `since` | adds a `Since: version` line to the doc comments | no |
+//! | `cast` | a raw annotation written on the line after the `@field`, e.g. `"@as integer"` | no |
+//! | `default` | adds a `Default: value` line to the start of the doc comments | no |
+//! | `range` | adds a `Range: range` line to the start of the doc comments, e.g. `"0..=255"` | no |
+//!
+//! _Note_: `PhantomData` fields are always skipped, the type parameters of the struct are added to the `@class` instead.
+//! A tuple struct with only `PhantomData` fields is an empty class, so it needs `allow_empty`
+//!
+//! _Note_: field names cannot contain a `.`, LuaLS has no way to annotate a nested field from its parent
+//!
+//! _Note_: a class without any fields (or methods) is an error, every field being ignored is usually a mistake. Use `#[anno(allow_empty)]` if it is intended. This is an error rather than a warning because derive macros cannot emit warnings on stable
 //!
 //! ## enums
 //! #### on the type
@@ -44,18 +127,57 @@
 //!
 //! | attribute | description | required |
 //! | --- | --- | --- |
-//! | `name` | allows you to rename the type | no |
+//! | `name` | allows you to rename the type, `rename` is the same | no |
+//! | `name_template` | names the type from a template, each `{}` is replaced by the rust name, e.g. `Lua{}` is `LuaFoo`. `name` takes precedence | no |
 //! | `self` | should the variant discriminants use this type? | no |
 //! | `alias`| allows you alias this variant to another type | no |
+//! | `as_alias` | emits an `@alias` of the variant values (e.g. `integer`) instead of the `@enum` | no |
+//! | `value_alias` | also emits an `@alias` of the variant values with this name | no |
+//! | `as_literal_alias` | emits an `@alias` that is a union of the variant values (e.g. `---| 0 # Low`) instead of the `@enum` | no |
+//! | `with_location` | records the `file:line` of each variant in `Variant::location` | no |
+//! | `case_insensitive_names` | variant names that only differ by case are duplicates | no |
+//! | `with_reverse` | also emits a `Name_reverse` table mapping the numeric values back to the variant names. It cannot be used with `self` or `alias` | no |
+//! | `header` | a raw annotation written on the line before the `@enum`. Can be repeated | no |
+//! | `doc` | appended to the doc comments | no |
+//! | `doc_replace` | replaces the doc comments | no |
+//! | `since` | adds a `Since: version` line to the doc comments | no |
 //!
 //! _Note_: `self` and `alias` are exclusive. 'alias' is the same as 'self' except you can change its /other/ name`
 //!
+//! _Note_: `as_alias`, `as_literal_alias` and `value_alias` are exclusive. Each value of `as_literal_alias` is commented with the name and docs of its variant
+//!
 //! #### on variants
 //! `#[anno(name = "name")]`
 //!
 //! | attribute | description | required |
 //! | --- | --- | --- |
-//! | `name` | allows you to rename the variant | no |
+//! | `name` | allows you to rename the variant, `rename` is the same | no |
+//! | `value` | use this string as the value of the variant, it cannot be used with `self` or `alias` | no |
+//! | `ignore` | skips this variant entirely, it can be a non-unit variant. `skip` is the same. Its [`AnnoEnum::variant_name`] is empty (`""`) | no |
+//! | `doc` | appended to the doc comments | no |
+//! | `doc_replace` | replaces the doc comments | no |
+//! | `since` | adds a `Since: version` line to the doc comments | no |
+//!
+//! _Note_: variant names cannot contain a `.`
+//!
+//! ## type aliases
+//! `#[anno(alias)]` can be put on a `type` alias, this generates an `@alias` of the aliased type
+//!
+//! | attribute | description | required |
+//! | --- | --- | --- |
+//! | `alias` | marks this as an alias | yes |
+//! | `name` | allows you to rename the alias, `rename` is the same | no |
+//! | `marker` | the name of the marker type, `FooAnno` for `type Foo` by default | no |
+//! | `lua_type` | the aliased lua type, otherwise it is guessed | no |
+//! | `reference` | the aliased type references other `Anno` types, the same as on a struct field | no |
+//! | `doc` | appended to the doc comments | no |
+//! | `doc_replace` | replaces the doc comments | no |
+//! | `since` | adds a `Since: version` line to the doc comments | no |
+//!
+//! An `Anno` impl on the alias would be on the aliased type, so the annotations are on a unit struct next to the alias instead,
+//! e.g. `type Scores = HashMap<String, i32>;` adds a `ScoresAnno` and is generated with `generate::<ScoresAnno>()`
+//!
+//! _Note_: the unit struct has no type parameters, so a generic alias like `type Pair<T> = (T, T);` is an error. Lifetimes are fine
 //!
 //! ## [`AnnoEnum`]
 //! This trait is generated for enums, it gives you the lua_name mapped to the enum variant
@@ -71,15 +193,53 @@
 //! })?;
 //! ```
 //!
+//! [`AnnoEnum::all_names`] gives you just the lua names, e.g. for validating input, and [`AnnoEnum::variant_values`] pairs them with their numeric values
+//!
 //! # Notes about enums
 //! - Currently only unit variants are supported.
 //! - Without `self` the variants start to count from 0
+//! - Ignored variants still count towards the numbering, they just aren't emitted
 //!
 //! ---
 //!
 //! You can manually number the variants
 //! If you do number them and let rust pick the others -- you can end up with duplicates.
 //! This is intended, lua "enums" aren't algrebiac data types (e.g. sum types), so aliasing is potentially desired
+//! Generating with [`GenerateOptions::diagnostics`] set adds a `-- note: duplicate discriminant` comment to the aliased variants
+//!
+//! # Lua names
+//! The derive also adds an inherent `LUA_NAME` const to the type, e.g. `Foo::LUA_NAME`.
+//! It is the same as `Foo::lua_type().name()` but it can be used in const contexts
+//!
+//! # Validating references
+//! A field can reference a type that was never generated, e.g. a misspelled `lua_type`.
+//! [`validate_all`] checks that every referenced type (including the ones in method and operator signatures) is a lua primitive or one of the types passed to it,
+//! and [`GenerateOptions::strict_refs`] makes the module generators fail instead of writing a dangling reference
+//!
+//! [`validate_types`] does the same, but it also takes a list of names that are declared elsewhere (e.g. in a hand-written stub file)
+//! and returns an [`UnknownType`] for each reference with the type, field and offending name
+//!
+//! [`validate_all`] also reports a field that is in a class twice, e.g. from an `inline` field with the same name as another field
+//!
+//! # Detecting changes
+//! [`diff_types`] compares two versions of a type and returns a [`TypeChange`] for each field or variant that was added, removed, renamed or changed, and for any other lua facing change like `exact` or `optional_key`,
+//! e.g. to fail CI when the lua facing surface changes unexpectedly. A snapshot of the old types can be loaded with the `serde` feature
+//!
+//! # Features
+//! | feature | description | default |
+//! | --- | --- | --- |
+//! | `std` | the `io::Write` generators, e.g. [`generate`] | yes |
+//! | `alloc` | the `fmt::Write` generators, e.g. [`generate_fmt`] | yes, through `std` |
+//! | `serde` | `serde::Serialize` and `serde::Deserialize` for the types, e.g. [`Type`], enables `alloc` | no |
+//! | `json` | the json generators, e.g. `generate_json`, enables `std` and `serde` | no |
+//! | `ron` | `generate_ron`, enables `std` and `serde` | no |
+//! | `toml` | `generate_toml`, enables `std` and `serde` | no |
+//!
+//! Without `std` this is `no_std`, the types and traits are always available
+//!
+//! The types only borrow `&'static` data, so deserializing them leaks the strings and slices.
+//! This is meant for loading a snapshot of the types, e.g. to diff it against the current ones.
+//! An inlined [`Field`] keeps the type that was serialized, as a [`LuaTypeFn::Static`]
 //!
 //! # Examples
 //!
@@ -121,8 +281,37 @@
 //! }
 //! ```
 //!
-pub use anno_lua_derive::Anno;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use anno_lua_derive::{anno, Anno};
+pub use anno_lua_impl::{
+    Alias, Anno, AnnoEnum, Class, Discriminant, Enum, Field, GenerateOptions, LuaTypeFn, Method,
+    Operator, Type, ValueAlias, Variant, Visitor,
+};
+
+#[doc(hidden)]
+pub use anno_lua_impl::__private;
+
+#[cfg(feature = "alloc")]
+pub use anno_lua_impl::{
+    diff_types, generate_alias_fmt, generate_class_fmt, generate_enum_fmt, generate_fmt,
+    generate_fmt_with, generate_module_fmt, generate_module_fmt_with, generate_stream_fmt,
+    generate_type_fmt, generate_type_fmt_with, generate_with_dependencies_fmt, validate_all,
+    validate_types, LuaType, Registry, TypeChange, UnknownType,
+};
+
+#[cfg(feature = "json")]
+pub use anno_lua_impl::{generate_json, generate_module_json};
+
+#[cfg(feature = "ron")]
+pub use anno_lua_impl::generate_ron;
+
+#[cfg(feature = "toml")]
+pub use anno_lua_impl::generate_toml;
+
+#[cfg(feature = "std")]
 pub use anno_lua_impl::{
-    generate, generate_class, generate_enum, generate_type, Anno, AnnoEnum, Class, Discriminant,
-    Enum, Field, Type, Variant,
+    generate, generate_alias, generate_class, generate_each_to_dir, generate_enum, generate_module,
+    generate_module_with, generate_stream, generate_type, generate_type_with, generate_with,
+    generate_with_dependencies,
 };
@@ -0,0 +1,54 @@
+#![cfg(feature = "alloc")]
+use std::collections::HashMap;
+
+use anno_lua::Anno;
+
+/// Scores by player name
+#[anno_lua::anno(alias)]
+type Scores = HashMap<String, i32>;
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Player {
+    name: String,
+}
+
+#[anno_lua::anno(alias, reference, name = "Players")]
+type Roster = Vec<Player>;
+
+#[anno_lua::anno(alias, marker = NameAlias, lua_type = "string")]
+type Name = String;
+
+#[test]
+fn foreign_types() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<ScoresAnno>(&mut out).unwrap();
+    anno_lua::generate_fmt::<RosterAnno>(&mut out).unwrap();
+    anno_lua::generate_fmt::<NameAlias>(&mut out).unwrap();
+
+    let expected = "\
+--- Scores by player name
+---@alias Scores table<string, integer>
+
+---@alias Players Player[]
+
+---@alias Name string
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn the_aliased_types_are_unchanged() {
+    let scores: Scores = HashMap::from([("a".to_string(), 1)]);
+    assert_eq!(scores["a"], 1);
+
+    // `Player` keeps its own impl and `String` keeps the builtin one
+    assert_eq!(Roster::new().len(), 0);
+    assert_eq!(<Player as Anno>::lua_type().name(), "Player");
+    assert_eq!(<Name as Anno>::lua_type().name(), "string");
+
+    assert_eq!(ScoresAnno::LUA_NAME, "Scores");
+    assert_eq!(RosterAnno::LUA_NAME, "Players");
+}
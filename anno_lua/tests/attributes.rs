@@ -0,0 +1,231 @@
+#![cfg(feature = "alloc")]
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[cfg_attr(all(), anno(name = "Settings"))]
+#[cfg_attr(any(), anno(name = "Unused"))]
+#[allow(dead_code)]
+struct Config {
+    #[cfg_attr(all(), anno(lua_type = "integer"))]
+    volume: u8,
+    #[anno(lua_type = "boolean")]
+    #[cfg_attr(all(), anno(name = "muted"))]
+    mute: bool,
+    #[cfg_attr(test, anno(ignore))]
+    cache: Vec<u8>,
+}
+
+#[test]
+fn cfg_attr() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Config>(&mut out).unwrap();
+
+    let expected = "\
+---@class Settings
+---@field volume integer
+---@field muted boolean
+Settings = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+const LOCATED: u32 = line!();
+#[derive(Anno)]
+#[anno(with_location)]
+#[allow(dead_code)]
+struct Located {
+    #[anno(lua_type = "number")]
+    x: f32,
+}
+
+#[derive(Anno)]
+#[anno(with_location)]
+#[allow(dead_code)]
+enum Side {
+    Left,
+}
+
+#[test]
+fn with_location() {
+    let anno_lua::Type::Class(class) = Located::lua_type() else {
+        panic!("expected a class")
+    };
+    let expected = format!("{}:{}", file!(), LOCATED + 6);
+    assert_eq!(class.fields[0].location, Some(&*expected));
+
+    let anno_lua::Type::Enum(side) = Side::lua_type() else {
+        panic!("expected an enum")
+    };
+    let expected = format!("{}:{}", file!(), LOCATED + 13);
+    assert_eq!(side.variants[0].location, Some(&*expected));
+
+    // the location is not part of the annotations
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Located>(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "---@class Located\n---@field x number\nLocated = { }\n\n"
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Profile {
+    // the key can be absent
+    #[anno(optional_key)]
+    nickname: String,
+    // the key is present, but the value can be nil
+    avatar: Option<String>,
+    #[anno(optional_key)]
+    banner: Option<String>,
+}
+
+#[test]
+fn optional_key() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Profile>(&mut out).unwrap();
+
+    let expected = "\
+---@class Profile
+---@field nickname? string
+---@field avatar string?
+---@field banner? string?
+Profile = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+/// A saved game
+#[derive(Anno)]
+#[anno(since = "1.2")]
+#[allow(dead_code)]
+struct Save {
+    #[anno(lua_type = "string")]
+    name: String,
+    /// When it was saved
+    #[anno(lua_type = "integer", since = "1.3")]
+    timestamp: u64,
+}
+
+#[derive(Anno)]
+#[anno(since = "1.0")]
+#[allow(dead_code)]
+enum Difficulty {
+    Easy,
+    /// For experts
+    #[anno(since = "2.0")]
+    Hard,
+}
+
+#[test]
+fn since() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Save>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Difficulty>(&mut out).unwrap();
+
+    let expected = "\
+--- A saved game
+--- Since: 1.2
+---@class Save
+---@field name string
+--- When it was saved
+--- Since: 1.3
+---@field timestamp integer
+Save = { }
+
+--- Since: 1.0
+---@enum Difficulty
+Difficulty = {
+    Easy = 0,
+    --- For experts
+    --- Since: 2.0
+    Hard = 1,
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Handle {
+    /// The raw id
+    #[anno(lua_type = "lightuserdata", cast = "@as integer")]
+    id: usize,
+}
+
+#[test]
+fn cast() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Handle>(&mut out).unwrap();
+
+    let expected = "\
+---@class Handle
+--- The raw id
+---@field id lightuserdata
+---@as integer
+Handle = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Volume {
+    /// How loud it is
+    #[anno(lua_type = "integer", default = "100")]
+    level: u8,
+    #[anno(lua_type = "boolean", default = "false")]
+    muted: bool,
+}
+
+#[test]
+fn default() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Volume>(&mut out).unwrap();
+
+    let expected = "\
+---@class Volume
+--- Default: 100
+--- How loud it is
+---@field level integer
+--- Default: false
+---@field muted boolean
+Volume = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Color {
+    /// The red channel
+    #[anno(lua_type = "integer", range = "0..=255")]
+    red: u8,
+    #[anno(lua_type = "integer", range = "-1..", default = "-1")]
+    layer: i32,
+}
+
+#[test]
+fn range() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Color>(&mut out).unwrap();
+
+    let expected = "\
+---@class Color
+--- Range: 0..=255
+--- The red channel
+---@field red integer
+--- Default: -1
+--- Range: -1..
+---@field layer integer
+Color = { }
+
+";
+    assert_eq!(out, expected);
+}
@@ -0,0 +1,24 @@
+// each test crate only uses some of these
+#![allow(dead_code)]
+
+use anno_lua::{Anno, Type};
+
+/// The lua names and types of the fields of a class
+pub fn field_types<T: Anno>() -> Vec<(&'static str, &'static str)> {
+    let Type::Class(class) = T::lua_type() else {
+        panic!("expected a class")
+    };
+    class
+        .fields
+        .iter()
+        .map(|field| (field.name, field.ty))
+        .collect()
+}
+
+/// The lua name and aliased type of an alias
+pub fn alias<T: Anno>() -> (&'static str, &'static str) {
+    let Type::Alias(alias) = T::lua_type() else {
+        panic!("expected an alias")
+    };
+    (alias.name, alias.ty)
+}
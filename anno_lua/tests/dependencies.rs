@@ -0,0 +1,68 @@
+#![cfg(feature = "alloc")]
+use anno_lua::{Anno, Registry};
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Root {
+    #[anno(reference, lua_type = "Child[]")]
+    children: Vec<Child>,
+    #[anno(reference, lua_type = "Root?")]
+    parent: Option<Box<Root>>,
+    #[anno(reference, lua_type = "table<string, Kind>")]
+    kinds: std::collections::HashMap<String, Kind>,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Child {
+    #[anno(reference, lua_type = "Kind")]
+    kind: Kind,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Kind {
+    A,
+    B,
+}
+
+#[test]
+fn registers_each_type_once() {
+    let mut registry = Registry::new();
+    registry.register::<Root>();
+
+    let names = registry
+        .types()
+        .iter()
+        .map(|ty| ty.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Root", "Child", "Kind"]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_skips_seen_names() {
+    let types = [
+        Kind::lua_type(),
+        Child::lua_type(),
+        Kind::lua_type(),
+        i32::lua_type(),
+    ];
+
+    let mut out = vec![];
+    anno_lua::generate_stream(&mut out, types).unwrap();
+
+    let expected = "\
+---@enum Kind
+Kind = {
+    A = 0,
+    B = 1,
+}
+
+---@class Child
+---@field kind Kind
+Child = { }
+
+";
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
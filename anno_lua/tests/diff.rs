@@ -0,0 +1,202 @@
+#![cfg(feature = "alloc")]
+use anno_lua::{diff_types, Anno, Class, Discriminant, Enum, Field, Type, TypeChange, Variant};
+
+mod old {
+    use anno_lua::Anno;
+
+    #[derive(Anno)]
+    #[anno(name = "User")]
+    #[allow(dead_code)]
+    pub struct User {
+        #[anno(lua_type = "integer")]
+        pub id: u32,
+        #[anno(lua_type = "string")]
+        pub name: String,
+        #[anno(lua_type = "integer")]
+        pub age: u8,
+        #[anno(lua_type = "string")]
+        pub email: String,
+    }
+
+    #[derive(Anno)]
+    #[anno(name = "Direction")]
+    #[allow(dead_code)]
+    pub enum Direction {
+        Up,
+        Down,
+        Left = 5,
+    }
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct User {
+    #[anno(lua_type = "integer")]
+    id: u32,
+    #[anno(lua_type = "string")]
+    display_name: String,
+    #[anno(lua_type = "number")]
+    age: f32,
+    #[anno(lua_type = "boolean")]
+    admin: bool,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Direction {
+    North,
+    Down,
+    Left = 6,
+    Right,
+}
+
+#[test]
+fn unchanged() {
+    assert!(diff_types(&User::lua_type(), &User::lua_type()).is_empty());
+}
+
+#[test]
+fn fields() {
+    let changes = diff_types(&old::User::lua_type(), &User::lua_type());
+    assert_eq!(
+        changes,
+        [
+            TypeChange::FieldRenamed {
+                from: "name",
+                to: "display_name"
+            },
+            TypeChange::FieldTypeChanged {
+                name: "age",
+                from: "integer",
+                to: "number"
+            },
+            TypeChange::FieldRemoved { name: "email" },
+            TypeChange::FieldAdded { name: "admin" },
+        ]
+    );
+
+    let changes = changes.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        [
+            "field `name` was renamed to `display_name`",
+            "field `age` changed from `integer` to `number`",
+            "field `email` was removed",
+            "field `admin` was added",
+        ]
+    );
+}
+
+#[test]
+fn variants() {
+    assert_eq!(
+        diff_types(&old::Direction::lua_type(), &Direction::lua_type()),
+        [
+            TypeChange::VariantRenamed {
+                from: "Up",
+                to: "North"
+            },
+            TypeChange::VariantValueChanged {
+                name: "Left",
+                from: Discriminant::Number(5),
+                to: Discriminant::Number(6)
+            },
+            TypeChange::VariantAdded { name: "Right" },
+        ]
+    );
+}
+
+#[test]
+fn kinds() {
+    assert_eq!(
+        diff_types(&User::lua_type(), &Direction::lua_type()),
+        [
+            TypeChange::Renamed {
+                from: "User",
+                to: "Direction"
+            },
+            TypeChange::KindChanged {
+                from: "class",
+                to: "enum"
+            },
+        ]
+    );
+}
+
+#[test]
+fn other_changes() {
+    const FIELDS: &[Field] = &[
+        Field::new("name", "string"),
+        Field::new("string", "integer"),
+    ];
+    const CHANGED: &[Field] = &[
+        Field {
+            optional_key: true,
+            ..Field::new("name", "string")
+        },
+        Field {
+            index: true,
+            ..Field::new("string", "integer")
+        },
+    ];
+    let old = Type::Class(Class::new("Item", FIELDS));
+    let new = Type::Class(Class {
+        exact: true,
+        generics: &["T"],
+        headers: &["@diagnostic disable"],
+        fields: CHANGED,
+        ..Class::new("Item", FIELDS)
+    });
+
+    let changes = diff_types(&old, &new);
+    assert_eq!(
+        changes,
+        [
+            TypeChange::Changed { what: "exact" },
+            TypeChange::Changed { what: "headers" },
+            TypeChange::Changed { what: "generics" },
+            TypeChange::FieldChanged {
+                name: "name",
+                what: "optional_key"
+            },
+            TypeChange::FieldChanged {
+                name: "string",
+                what: "index"
+            },
+        ]
+    );
+
+    let changes = changes.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        [
+            "`exact` changed",
+            "`headers` changed",
+            "`generics` changed",
+            "field `name` changed its `optional_key`",
+            "field `string` changed its `index`",
+        ]
+    );
+
+    const VARIANTS: &[Variant] = &[Variant::new("Up", Discriminant::Number(0))];
+    let old = Type::Enum(Enum::new("Direction", VARIANTS));
+    let new = Type::Enum(Enum {
+        reverse: true,
+        ..Enum::new("Direction", VARIANTS)
+    });
+    assert_eq!(
+        diff_types(&old, &new),
+        [TypeChange::Changed { what: "reverse" }]
+    );
+}
+
+#[test]
+fn docs_are_not_compared() {
+    let old = Type::Class(Class::new("Item", &[]));
+    let new = Type::Class(Class {
+        docs: &["An item"],
+        ..Class::new("Item", &[])
+    });
+    assert_ne!(old, new);
+    assert!(diff_types(&old, &new).is_empty());
+}
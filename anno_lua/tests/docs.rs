@@ -0,0 +1,285 @@
+#![cfg(feature = "alloc")]
+use anno_lua::Anno;
+
+/// A greeter
+///
+/// ```lua
+/// local greeter = Greeter.new()
+/// if greeter then
+///     print(greeter.name)
+/// end
+/// ```
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Greeter {
+    /// The name to greet
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[test]
+fn fenced_docs_keep_indentation() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Greeter>(&mut out).unwrap();
+
+    let expected = "\
+--- A greeter
+--- 
+--- ```lua
+--- local greeter = Greeter.new()
+--- if greeter then
+---     print(greeter.name)
+--- end
+--- ```
+---@class Greeter
+--- The name to greet
+---@field name string
+Greeter = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[doc = "Copied from windows\r\n\r\nWith a blank line\r"]
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Crlf {
+    #[doc = " The first line\r\n The second line\r"]
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn carriage_returns_are_stripped() {
+    let mut out = vec![];
+    anno_lua::generate::<Crlf>(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    let expected = "\
+--- Copied from windows
+--- 
+--- With a blank line
+---@class Crlf
+--- The first line
+--- The second line
+---@field name string
+Crlf = { }
+
+";
+    assert_eq!(out, expected);
+    assert!(!out.contains('\r'));
+
+    // hand written docs are split the same way
+    let ty = anno_lua::Type::Class(anno_lua::Class {
+        docs: &["A\r\nB\r"],
+        ..anno_lua::Class::new("Id", &[])
+    });
+    let mut out = vec![];
+    anno_lua::generate_type(&mut out, &ty).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "--- A\n--- B\n---@class Id\nId = { }\n\n"
+    );
+
+    let ty = anno_lua::Type::Alias(anno_lua::Alias {
+        docs: &["A\r\nB\r"],
+        ..anno_lua::Alias::new("Id", "integer")
+    });
+    assert_eq!(ty.to_lua_string(), "--- A\n--- B\n---@alias Id integer\n\n");
+}
+
+/// Wraps a `std::fs::File`
+#[derive(Anno)]
+#[anno(doc_replace = "An open file")]
+#[allow(dead_code)]
+struct FileHandle {
+    /// The path
+    #[anno(lua_type = "string", doc = "Relative to the working directory")]
+    path: String,
+    /// Uses `Ordering::Relaxed`
+    #[anno(lua_type = "integer", doc_replace = "How many bytes were read")]
+    read: u64,
+}
+
+/// How a file is opened
+#[derive(Anno)]
+#[anno(doc = "Defaults to `Read`")]
+#[allow(dead_code)]
+enum Mode {
+    /// Only reading
+    #[anno(doc = "The file must exist")]
+    Read,
+    /// Maps to `OpenOptions::append`
+    #[anno(doc_replace = "Writes go to the end")]
+    Append,
+}
+
+#[test]
+fn doc_overrides() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<FileHandle>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Mode>(&mut out).unwrap();
+
+    let expected = "\
+--- An open file
+---@class FileHandle
+--- The path
+--- Relative to the working directory
+---@field path string
+--- How many bytes were read
+---@field read integer
+FileHandle = { }
+
+--- How a file is opened
+--- Defaults to `Read`
+---@enum Mode
+Mode = {
+    --- Only reading
+    --- The file must exist
+    Read = 0,
+    --- Writes go to the end
+    Append = 1,
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+/// Some directions
+#[derive(Anno)]
+#[anno(self)]
+#[allow(dead_code)]
+enum Direction {
+    /// Going up
+    ///
+    /// Towards the sky
+    Up,
+    Down,
+    /// The other way
+    #[anno(name = "left")]
+    Left,
+}
+
+#[test]
+fn named_discriminant_docs() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Direction>(&mut out).unwrap();
+
+    let expected = "\
+--- Some directions
+---@enum Direction
+Direction = {
+    --- Going up
+    --- 
+    --- Towards the sky
+    Up = Direction,
+    Down = Direction,
+    --- The other way
+    left = Direction,
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn to_lua_string() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Greeter>(&mut out).unwrap();
+    assert_eq!(Greeter::lua_type().to_lua_string(), out);
+}
+
+/// Loaded from the config
+#[derive(Anno)]
+#[anno(guess, header = "@diagnostic disable: missing-fields")]
+#[anno(header = "@deprecated")]
+#[allow(dead_code)]
+struct Config {
+    path: String,
+}
+
+#[test]
+fn headers_after_docs() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Config>(&mut out).unwrap();
+
+    let expected = "\
+--- Loaded from the config
+---@diagnostic disable: missing-fields
+---@deprecated
+---@class Config
+---@field path string
+Config = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn debug_tree() {
+    let expected = "\
+class Greeter
+  docs: \"A greeter\"
+  docs: \"\"
+  docs: \"```lua\"
+  docs: \"local greeter = Greeter.new()\"
+  docs: \"if greeter then\"
+  docs: \"    print(greeter.name)\"
+  docs: \"end\"
+  docs: \"```\"
+  field name: string
+    docs: \"The name to greet\"
+";
+    assert_eq!(Greeter::lua_type().debug_tree(), expected);
+
+    let expected = "\
+enum Direction
+  docs: \"Some directions\"
+  variant Up = Direction
+    docs: \"Going up\"
+    docs: \"\"
+    docs: \"Towards the sky\"
+  variant Down = Direction
+  variant left = Direction
+    docs: \"The other way\"
+";
+    assert_eq!(Direction::lua_type().debug_tree(), expected);
+}
+
+/// A position
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Spaced {
+    /// From the left
+    x: f32,
+    /// From the top
+    y: f32,
+    z: f32,
+}
+
+#[test]
+fn field_spacing() {
+    let mut out = String::new();
+    let options = anno_lua::GenerateOptions {
+        field_spacing: true,
+        ..anno_lua::GenerateOptions::new()
+    };
+    anno_lua::generate_fmt_with::<Spaced>(&mut out, &options).unwrap();
+
+    let expected = "\
+--- A position
+---@class Spaced
+
+--- From the left
+---@field x number
+
+--- From the top
+---@field y number
+---@field z number
+Spaced = { }
+
+";
+    assert_eq!(out, expected);
+}
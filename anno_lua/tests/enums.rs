@@ -0,0 +1,294 @@
+#![cfg(feature = "alloc")]
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Key {
+    #[anno(value = "enter")]
+    Enter,
+    #[anno(value = "\"quoted\"")]
+    Quote,
+    #[anno(value = "back\\slash")]
+    Backslash,
+}
+
+#[test]
+fn string_values() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Key>(&mut out).unwrap();
+
+    let expected = "\
+---@enum Key
+Key = {
+    Enter = \"enter\",
+    Quote = \"\\\"quoted\\\"\",
+    Backslash = \"back\\\\slash\",
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+/// Where to go
+#[derive(Anno)]
+#[anno(as_alias)]
+#[allow(dead_code)]
+enum Heading {
+    North,
+    South,
+}
+
+#[derive(Anno)]
+#[anno(value_alias = "SpeedValue")]
+#[allow(dead_code)]
+enum Speed {
+    Slow,
+    Fast,
+}
+
+#[derive(Anno)]
+#[anno(value_alias = "ColorName")]
+#[allow(dead_code)]
+enum Color {
+    #[anno(value = "red")]
+    Red,
+    #[anno(value = "blue")]
+    Blue,
+}
+
+#[derive(Anno)]
+#[anno(self, value_alias = "ToggleValue")]
+#[allow(dead_code)]
+enum Toggle {
+    On,
+    Off,
+}
+
+#[test]
+fn as_alias() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Heading>(&mut out).unwrap();
+
+    let expected = "\
+--- Where to go
+---@alias Heading integer
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn value_alias() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Speed>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Color>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Toggle>(&mut out).unwrap();
+
+    let expected = "\
+---@enum Speed
+Speed = {
+    Slow = 0,
+    Fast = 1,
+}
+
+---@alias SpeedValue integer
+
+---@enum Color
+Color = {
+    Red = \"red\",
+    Blue = \"blue\",
+}
+
+---@alias ColorName string
+
+---@enum Toggle
+Toggle = {
+    On = Toggle,
+    Off = Toggle,
+}
+
+---@alias ToggleValue Toggle
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[anno(with_reverse)]
+#[allow(dead_code)]
+enum Level {
+    Low,
+    #[anno(name = "mid")]
+    Medium = 5,
+    High = 6,
+}
+
+#[test]
+fn reverse_table() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Level>(&mut out).unwrap();
+
+    let expected = "\
+---@enum Level
+Level = {
+    Low = 0,
+    mid = 5,
+    High = 6,
+}
+
+---@type table<integer, string>
+Level_reverse = {
+    [0] = \"Low\",
+    [5] = \"mid\",
+    [6] = \"High\",
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn variant_by_name() {
+    let anno_lua::Type::Enum(level) = Level::lua_type() else {
+        panic!("expected an enum")
+    };
+    assert_eq!(
+        level.variant("mid").map(|variant| variant.discriminant),
+        Some(anno_lua::Discriminant::Number(5))
+    );
+    assert!(level.variant("Medium").is_none());
+}
+
+#[test]
+fn lua_name_const() {
+    const NAME: &str = Level::LUA_NAME;
+    assert_eq!(NAME, "Level");
+}
+
+/// How loud it is
+#[derive(Anno)]
+#[anno(as_literal_alias)]
+#[allow(dead_code)]
+enum Volume {
+    /// Nothing at all
+    Mute,
+    #[anno(name = "quiet")]
+    Low = 3,
+    /// Too loud,
+    /// turn it down
+    High = 10,
+}
+
+#[test]
+fn literal_alias() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Volume>(&mut out).unwrap();
+
+    let expected = "\
+--- How loud it is
+---@alias Volume
+---| 0 # Mute: Nothing at all
+---| 3 # quiet
+---| 10 # High: Too loud, turn it down
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno, Copy, Clone)]
+#[anno(name = "Dir", self)]
+#[allow(dead_code)]
+enum Direction {
+    Up,
+    Down,
+}
+
+#[test]
+fn quoted_named_discriminants() {
+    let options = anno_lua::GenerateOptions {
+        quote_named_discriminants: true,
+        ..anno_lua::GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_fmt_with::<Direction>(&mut out, &options).unwrap();
+
+    let expected = "\
+---@enum Dir
+Dir = {
+    Up = \"Dir\",
+    Down = \"Dir\",
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn no_trailing_comma() {
+    let options = anno_lua::GenerateOptions {
+        trailing_comma: false,
+        ..anno_lua::GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_fmt_with::<Level>(&mut out, &options).unwrap();
+
+    let expected = "\
+---@enum Level
+Level = {
+    Low = 0,
+    mid = 5,
+    High = 6
+}
+
+---@type table<integer, string>
+Level_reverse = {
+    [0] = \"Low\",
+    [5] = \"mid\",
+    [6] = \"High\"
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn all_names() {
+    use anno_lua::AnnoEnum as _;
+
+    let names = Level::all_names().collect::<Vec<_>>();
+    assert_eq!(names, ["Low", "mid", "High"]);
+    assert!(Level::all_names().any(|name| name == "mid"));
+    assert!(!Level::all_names().any(|name| name == "Medium"));
+}
+
+#[derive(Anno)]
+#[anno(self)]
+#[allow(dead_code)]
+enum Named {
+    One,
+    Two,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Partial {
+    First,
+    #[anno(ignore)]
+    Second(u8),
+    Third,
+}
+
+#[test]
+fn variant_values() {
+    use anno_lua::AnnoEnum as _;
+
+    let values = Level::variant_values().collect::<Vec<_>>();
+    assert_eq!(values, [("Low", 0), ("mid", 5), ("High", 6)]);
+
+    // ignored variants still count towards the numbering
+    let values = Partial::variant_values().collect::<Vec<_>>();
+    assert_eq!(values, [("First", 0), ("Third", 2)]);
+
+    // named discriminants are skipped
+    assert_eq!(Named::variant_values().count(), 0);
+}
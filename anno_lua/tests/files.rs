@@ -0,0 +1,97 @@
+#![cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+use anno_lua::{Alias, Anno, Type};
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Player {
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Team {
+    Red,
+    Blue,
+}
+
+// a fresh directory for each test, removed when dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("anno_lua_{name}_{id}", id = std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        Self(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn file_names(dir: &Path) -> Vec<String> {
+    let mut files = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn read(dir: &Path, file: &str) -> String {
+    std::fs::read_to_string(dir.join(file)).unwrap()
+}
+
+#[test]
+fn generate_each_to_dir() {
+    let dir = TempDir::new("each");
+    let types = [
+        Player::lua_type(),
+        Team::lua_type(),
+        Type::Alias(Alias::new("ids/list", "integer[]")),
+    ];
+    // the nested directory is created
+    let nested = dir.0.join("types");
+    anno_lua::generate_each_to_dir(&nested, &types).unwrap();
+
+    assert_eq!(
+        file_names(&nested),
+        ["Player.lua", "Team.lua", "ids_list.lua"]
+    );
+    assert_eq!(
+        read(&nested, "Player.lua"),
+        "---@class Player\n---@field name string\nPlayer = { }\n\n"
+    );
+    let mut team = String::new();
+    anno_lua::generate_type_fmt(&mut team, &Team::lua_type()).unwrap();
+    assert_eq!(read(&nested, "Team.lua"), team);
+    assert_eq!(
+        read(&nested, "ids_list.lua"),
+        "---@alias ids/list integer[]\n\n"
+    );
+}
+
+#[test]
+fn same_file_name() {
+    let dir = TempDir::new("same");
+    let types = [
+        Player::lua_type(),
+        Type::Alias(Alias::new("a/b", "integer")),
+        Type::Alias(Alias::new("a:b", "string")),
+    ];
+    let err = anno_lua::generate_each_to_dir(&dir.0, &types).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "`a/b` and `a:b` would both be written to the same file"
+    );
+
+    // nothing was written
+    assert!(!dir.0.exists());
+}
@@ -0,0 +1,42 @@
+#![cfg(all(feature = "ron", feature = "toml"))]
+use anno_lua::{Anno, Type};
+
+/// A point on the screen
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Point {
+    #[anno(lua_type = "number")]
+    x: f32,
+    #[anno(lua_type = "number?", optional_key)]
+    y: Option<f32>,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Direction {
+    Up = 1,
+    #[anno(name = "down")]
+    Down = 2,
+}
+
+#[test]
+fn ron() {
+    for ty in [Point::lua_type(), Direction::lua_type()] {
+        let mut out = vec![];
+        anno_lua::generate_ron(&mut out, &ty).unwrap();
+
+        let loaded: Type = ron::de::from_bytes(&out).unwrap();
+        assert_eq!(loaded, ty);
+    }
+}
+
+#[test]
+fn toml() {
+    for ty in [Point::lua_type(), Direction::lua_type()] {
+        let mut out = vec![];
+        anno_lua::generate_toml(&mut out, &ty).unwrap();
+
+        let loaded: Type = toml::from_slice(&out).unwrap();
+        assert_eq!(loaded, ty);
+    }
+}
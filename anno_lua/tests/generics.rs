@@ -0,0 +1,119 @@
+#![cfg(feature = "alloc")]
+use std::marker::PhantomData;
+
+use anno_lua::{Anno, AnnoEnum as _};
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Handle<T>
+where
+    T: Send,
+{
+    #[anno(lua_type = "integer")]
+    id: u32,
+    #[anno(ignore)]
+    marker: PhantomData<T>,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Name<T: AsRef<str>>(#[anno(lua_type = "string")] T);
+
+#[derive(Anno)]
+#[anno(transparent)]
+#[allow(dead_code)]
+struct Key<'a, T>
+where
+    T: ?Sized,
+{
+    #[anno(lua_type = "string")]
+    key: &'a T,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum State<T>
+where
+    T: Clone,
+{
+    Idle,
+    Running,
+    #[anno(ignore)]
+    Other(PhantomData<T>),
+}
+
+#[test]
+fn where_clauses() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Handle<String>>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Name<String>>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Key<str>>(&mut out).unwrap();
+    anno_lua::generate_fmt::<State<String>>(&mut out).unwrap();
+
+    let expected = "\
+---@class Handle<T>
+---@field id integer
+Handle = { }
+
+---@alias Name string
+
+---@alias Key string
+
+---@enum State
+State = {
+    Idle = 0,
+    Running = 1,
+}
+
+";
+    assert_eq!(out, expected);
+    assert_eq!(State::<String>::Running.variant_name(), "Running");
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Borrowed<'a, 'b: 'a> {
+    name: &'a str,
+    tags: &'b [&'a str],
+    parent: Option<&'a Borrowed<'a, 'b>>,
+    #[anno(reference)]
+    handle: &'b Handle<u8>,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Token<'a> {
+    Eof,
+    #[anno(ignore)]
+    Word(&'a str),
+}
+
+#[test]
+fn lifetimes() {
+    let mut out = String::new();
+    anno_lua::generate_with_dependencies_fmt::<Borrowed>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Token>(&mut out).unwrap();
+
+    let expected = "\
+---@class Borrowed
+---@field name string
+---@field tags string[]
+---@field parent Borrowed?
+---@field handle Handle<integer>
+Borrowed = { }
+
+---@class Handle<T>
+---@field id integer
+Handle = { }
+
+---@enum Token
+Token = {
+    Eof = 0,
+}
+
+";
+    assert_eq!(out, expected);
+    // an ignored variant has no lua name
+    assert_eq!(Token::Word("hello").variant_name(), "");
+}
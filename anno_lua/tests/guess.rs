@@ -0,0 +1,435 @@
+#![cfg(feature = "alloc")]
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::{NonZero, NonZeroI64, NonZeroU32, NonZeroUsize},
+};
+
+use anno_lua::Anno;
+
+mod common;
+use common::field_types;
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct NonZeros {
+    bare: NonZeroI64,
+    generic: NonZero<u8>,
+    optional: Option<NonZeroU32>,
+    list: Vec<NonZeroUsize>,
+    nested: Option<Vec<NonZero<i16>>>,
+    map: std::collections::HashMap<NonZeroU32, Box<NonZeroU32>>,
+}
+
+#[test]
+fn non_zero_composes() {
+    assert_eq!(
+        field_types::<NonZeros>(),
+        [
+            ("bare", "integer"),
+            ("generic", "integer"),
+            ("optional", "integer?"),
+            ("list", "integer[]"),
+            ("nested", "integer[]?"),
+            ("map", "table<integer, integer>"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct References<'a> {
+    name: &'a str,
+    nickname: Option<&'a str>,
+    items: &'a [i32],
+    scores: &'a mut Vec<f32>,
+}
+
+#[test]
+fn references_are_unwrapped() {
+    assert_eq!(
+        field_types::<References>(),
+        [
+            ("name", "string"),
+            ("nickname", "string?"),
+            ("items", "integer[]"),
+            ("scores", "number[]"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Slices<'a> {
+    borrowed: &'a [String],
+    boxed: Box<[u32]>,
+    bytes: Box<[u8]>,
+    mutable: &'a mut [bool],
+}
+
+#[test]
+fn slices_are_lists() {
+    assert_eq!(
+        field_types::<Slices>(),
+        [
+            ("borrowed", "string[]"),
+            ("boxed", "integer[]"),
+            ("bytes", "integer[]"),
+            ("mutable", "boolean[]"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Untyped {
+    id: u32,
+    // a guess would be `string[]`, but any value is allowed
+    #[anno(no_guess)]
+    extra: Vec<String>,
+}
+
+#[test]
+fn no_guess() {
+    assert_eq!(
+        field_types::<Untyped>(),
+        [("id", "integer"), ("extra", "any")]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Nested<'a> {
+    maps: Vec<HashMap<String, i32>>,
+    optional: Vec<Option<HashMap<String, Vec<i32>>>>,
+    deep: Option<Vec<BTreeMap<String, Vec<bool>>>>,
+    slices: Option<&'a [&'a [f64]]>,
+}
+
+#[test]
+fn nested_containers() {
+    assert_eq!(
+        field_types::<Nested>(),
+        [
+            ("maps", "table<string, integer>[]"),
+            ("optional", "table<string, integer[]>?[]"),
+            ("deep", "table<string, boolean[]>[]?"),
+            ("slices", "number[][]?"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Mappings {
+    string: String,
+    str: &'static str,
+    bool: bool,
+    i8: i8,
+    i16: i16,
+    i32: i32,
+    i64: i64,
+    isize: isize,
+    u8: u8,
+    u16: u16,
+    u32: u32,
+    u64: u64,
+    usize: usize,
+    f32: f32,
+    f64: f64,
+    path: std::string::String,
+    option: Option<bool>,
+    vec: Vec<String>,
+    array: [f32; 3],
+    tuple: (String, u8),
+    unit: (),
+    map: BTreeMap<String, f64>,
+    object: Box<dyn std::fmt::Debug>,
+    pointer: *const u8,
+}
+
+#[test]
+fn mappings() {
+    assert_eq!(
+        field_types::<Mappings>(),
+        [
+            ("string", "string"),
+            ("str", "string"),
+            ("bool", "boolean"),
+            ("i8", "integer"),
+            ("i16", "integer"),
+            ("i32", "integer"),
+            ("i64", "integer"),
+            ("isize", "integer"),
+            ("u8", "integer"),
+            ("u16", "integer"),
+            ("u32", "integer"),
+            ("u64", "integer"),
+            ("usize", "integer"),
+            ("f32", "number"),
+            ("f64", "number"),
+            ("path", "string"),
+            ("option", "boolean?"),
+            ("vec", "string[]"),
+            ("array", "number[]"),
+            ("tuple", "[string, integer]"),
+            ("unit", "nil"),
+            ("map", "table<string, number>"),
+            ("object", "any"),
+            ("pointer", "any"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess, name = "TreeNode")]
+#[allow(dead_code)]
+struct Node {
+    next: Option<Box<Node>>,
+    children: Vec<Self>,
+    parent: Option<&'static Self>,
+}
+
+#[test]
+fn self_references_use_the_lua_name() {
+    assert_eq!(
+        field_types::<Node>(),
+        [
+            ("next", "TreeNode?"),
+            ("children", "TreeNode[]"),
+            ("parent", "TreeNode?"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Direction {
+    Up,
+    Down,
+}
+
+#[derive(Anno)]
+#[anno(name = "Dir")]
+#[allow(dead_code)]
+enum Renamed {
+    Left,
+    Right,
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Player {
+    #[anno(reference)]
+    facing: Direction,
+    #[anno(reference)]
+    history: Vec<Direction>,
+    #[anno(reference)]
+    next: Option<Direction>,
+    #[anno(reference, lua_type = "Dir[]")]
+    renamed: Vec<Renamed>,
+    idle: std::time::Duration,
+    save: Option<std::path::PathBuf>,
+}
+
+#[test]
+fn references_use_their_name() {
+    assert_eq!(
+        field_types::<Player>(),
+        [
+            ("facing", "Direction"),
+            ("history", "Direction[]"),
+            ("next", "Direction?"),
+            ("renamed", "Dir[]"),
+            ("idle", "any"),
+            ("save", "any"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Callbacks {
+    handler: Box<dyn Fn()>,
+    filter: Option<Box<dyn FnMut(u32) -> bool + Send>>,
+    pointer: fn(i32) -> i32,
+    other: Box<dyn std::any::Any>,
+}
+
+#[test]
+fn closures_are_functions() {
+    assert_eq!(
+        field_types::<Callbacks>(),
+        [
+            ("handler", "function"),
+            ("filter", "function?"),
+            ("pointer", "function"),
+            ("other", "any"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct BoxedStrings {
+    name: Box<str>,
+    alias: Option<Box<str>>,
+}
+
+#[test]
+fn boxed_str_is_a_string() {
+    assert_eq!(
+        field_types::<BoxedStrings>(),
+        [("name", "string"), ("alias", "string?")]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess, bytes_as_string)]
+#[allow(dead_code)]
+struct Blob {
+    data: Vec<u8>,
+    slice: &'static [u8],
+    array: [u8; 4],
+    boxed: Option<Box<[u8]>>,
+    numbers: Vec<u16>,
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Bytes {
+    data: Vec<u8>,
+}
+
+#[test]
+fn bytes_as_string() {
+    assert_eq!(
+        field_types::<Blob>(),
+        [
+            ("data", "string"),
+            ("slice", "string"),
+            ("array", "string"),
+            ("boxed", "string?"),
+            ("numbers", "integer[]"),
+        ]
+    );
+    assert_eq!(field_types::<Bytes>(), [("data", "integer[]")]);
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Instantiated {
+    #[anno(reference)]
+    items: Container<String>,
+    #[anno(reference)]
+    pairs: Vec<Pair<u32, Option<bool>>>,
+    #[anno(reference)]
+    borrowed: Borrowed<'static>,
+    unknown: Container<*const u8>,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Container<T>(T);
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Pair<K, V>(K, V);
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Borrowed<'a>(&'a str);
+
+#[test]
+fn generic_instantiation() {
+    assert_eq!(
+        field_types::<Instantiated>(),
+        [
+            ("items", "Container<string>"),
+            ("pairs", "Pair<integer, boolean?>[]"),
+            ("borrowed", "Borrowed"),
+            ("unknown", "any"),
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Wrapper<T> {
+    value: T,
+    values: Vec<Option<T>>,
+}
+
+#[test]
+fn type_parameters_use_their_name() {
+    assert_eq!(
+        field_types::<Wrapper<u8>>(),
+        [("value", "T"), ("values", "T?[]")]
+    );
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Facing(#[anno(reference)] Option<Direction>);
+
+#[test]
+fn tuple_references() {
+    let mut out = String::new();
+    anno_lua::generate_with_dependencies_fmt::<Facing>(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "\
+---@alias Facing Direction?
+
+---@enum Direction
+Direction = {
+    Up = 0,
+    Down = 1,
+}
+
+"
+    );
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Shared<'a> {
+    name: std::sync::Arc<str>,
+    counts: std::rc::Rc<std::cell::RefCell<Vec<u32>>>,
+    label: std::borrow::Cow<'a, str>,
+    items: std::borrow::Cow<'a, [bool]>,
+    tags: std::collections::HashSet<String>,
+    ids: std::collections::BTreeSet<u64>,
+    queue: std::collections::VecDeque<f32>,
+    locked: std::sync::Mutex<String>,
+    #[anno(reference)]
+    facing: std::rc::Rc<Direction>,
+}
+
+#[test]
+fn pointers_and_sets() {
+    assert_eq!(
+        field_types::<Shared>(),
+        [
+            ("name", "string"),
+            ("counts", "integer[]"),
+            ("label", "string"),
+            ("items", "boolean[]"),
+            ("tags", "string[]"),
+            ("ids", "integer[]"),
+            ("queue", "number[]"),
+            ("locked", "any"),
+            ("facing", "Direction"),
+        ]
+    );
+}
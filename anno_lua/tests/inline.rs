@@ -0,0 +1,137 @@
+#![cfg(feature = "alloc")]
+use anno_lua::{Anno, GenerateOptions};
+
+/// Where something is
+#[derive(Anno, serde::Serialize)]
+#[anno(guess, exact, rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Position {
+    /// From the left
+    pos_x: f32,
+    pos_y: f32,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Widget {
+    #[anno(lua_type = "string")]
+    name: String,
+    #[anno(inline)]
+    position: Position,
+    #[anno(lua_type = "boolean")]
+    visible: bool,
+}
+
+#[test]
+fn nested_settings_are_kept() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Widget>(&mut out).unwrap();
+
+    let expected = "\
+---@class Widget
+---@field name string
+--- From the left
+---@field posX number
+---@field posY number
+---@field visible boolean
+Widget = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Overlapping {
+    #[anno(name = "posX")]
+    x: f32,
+    #[anno(inline)]
+    position: Position,
+}
+
+#[test]
+fn duplicates_are_skipped() {
+    let mut out = String::new();
+    let options = GenerateOptions {
+        diagnostics: true,
+        ..GenerateOptions::new()
+    };
+    anno_lua::generate_fmt_with::<Overlapping>(&mut out, &options).unwrap();
+
+    let expected = "\
+---@class Overlapping
+---@field posX number
+-- note: skipped a duplicate field `posX`
+---@field posY number
+Overlapping = { }
+
+";
+    assert_eq!(out, expected);
+
+    let errors = anno_lua::validate_all(&[Overlapping::lua_type()]).unwrap_err();
+    assert_eq!(errors, ["`Overlapping.posX` is a duplicate field"]);
+}
+
+#[derive(Anno, serde::Serialize)]
+#[anno(guess, use_serde)]
+#[allow(dead_code)]
+struct Sprite {
+    name: String,
+    #[serde(flatten)]
+    position: Position,
+    #[serde(flatten, rename = "ignored")]
+    extra: std::collections::HashMap<String, i32>,
+}
+
+#[test]
+fn serde_flatten() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Sprite>(&mut out).unwrap();
+
+    let expected = "\
+---@class Sprite
+---@field name string
+--- From the left
+---@field posX number
+---@field posY number
+---@field [string] integer
+Sprite = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[anno(exact)]
+#[allow(dead_code)]
+struct Strict {
+    #[anno(lua_type = "string")]
+    label: String,
+    #[anno(inline)]
+    sprite: Sprite,
+}
+
+#[test]
+fn exact_with_an_inlined_index_signature() {
+    let mut out = String::new();
+    let options = GenerateOptions {
+        diagnostics: true,
+        ..GenerateOptions::new()
+    };
+    anno_lua::generate_fmt_with::<Strict>(&mut out, &options).unwrap();
+
+    let expected = "\
+---@class Strict
+-- note: `(exact)` was dropped because of the index signature `[string]`
+---@field label string
+---@field name string
+--- From the left
+---@field posX number
+---@field posY number
+---@field [string] integer
+Strict = { }
+
+";
+    assert_eq!(out, expected);
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "json")]
+use anno_lua::Anno;
+
+/// A point on the screen
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Point {
+    #[anno(lua_type = "number")]
+    x: f32,
+    #[anno(lua_type = "number?", optional_key)]
+    y: Option<f32>,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Direction {
+    Up = 1,
+    Down = 2,
+}
+
+#[test]
+fn class() {
+    let mut out = vec![];
+    anno_lua::generate_json(&mut out, &Point::lua_type()).unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let class = &json["Class"];
+    assert_eq!(class["name"], "Point");
+    assert_eq!(class["docs"], serde_json::json!(["A point on the screen"]));
+    assert_eq!(class["fields"][0]["name"], "x");
+    assert_eq!(class["fields"][0]["ty"], "number");
+    assert_eq!(class["fields"][1]["optional_key"], true);
+}
+
+#[test]
+fn module() {
+    let mut out = vec![];
+    anno_lua::generate_module_json(&mut out, &[Point::lua_type(), Direction::lua_type()]).unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json[0]["Class"]["name"], "Point");
+
+    let variants = &json[1]["Enum"]["variants"];
+    assert_eq!(variants[0]["name"], "Up");
+    assert_eq!(
+        variants[0]["discriminant"],
+        serde_json::json!({ "Number": 1 })
+    );
+}
+
+#[test]
+fn round_trip() {
+    let types = [Point::lua_type(), Direction::lua_type()];
+
+    let mut out = vec![];
+    anno_lua::generate_module_json(&mut out, &types).unwrap();
+
+    let loaded: Vec<anno_lua::Type> = serde_json::from_slice(&out).unwrap();
+    assert_eq!(loaded, types);
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Sprite {
+    #[anno(lua_type = "string")]
+    name: String,
+    #[anno(inline)]
+    position: Point,
+}
+
+#[test]
+fn round_trip_inline() {
+    let mut out = vec![];
+    anno_lua::generate_json(&mut out, &Sprite::lua_type()).unwrap();
+
+    // the inlined type is kept, so nothing changed
+    let loaded: anno_lua::Type = serde_json::from_slice(&out).unwrap();
+    assert_eq!(loaded, Sprite::lua_type());
+    assert!(anno_lua::diff_types(&loaded, &Sprite::lua_type()).is_empty());
+    assert_eq!(loaded.to_lua_string(), Sprite::lua_type().to_lua_string());
+}
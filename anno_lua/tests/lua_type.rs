@@ -0,0 +1,98 @@
+#![cfg(feature = "alloc")]
+use anno_lua::LuaType;
+
+fn name(s: &str) -> LuaType {
+    LuaType::Name(s.to_string())
+}
+
+#[test]
+fn parse() {
+    assert_eq!(LuaType::parse("integer"), name("integer"));
+    assert_eq!(
+        LuaType::parse("string[]?"),
+        LuaType::Optional(Box::new(LuaType::Array(Box::new(name("string")))))
+    );
+    assert_eq!(
+        LuaType::parse("table<string, integer[]>"),
+        LuaType::Table(
+            Box::new(name("string")),
+            Box::new(LuaType::Array(Box::new(name("integer"))))
+        )
+    );
+    assert_eq!(
+        LuaType::parse("[integer, table<string, boolean>]"),
+        LuaType::Tuple(vec![
+            name("integer"),
+            LuaType::Table(Box::new(name("string")), Box::new(name("boolean")))
+        ])
+    );
+    assert_eq!(
+        LuaType::parse("(\"a\" | \"b|c\")[]"),
+        LuaType::Array(Box::new(LuaType::Union(vec![
+            name("\"a\""),
+            name("\"b|c\"")
+        ])))
+    );
+    assert_eq!(
+        LuaType::parse("fun(self, n: integer): string"),
+        name("fun(self, n: integer): string")
+    );
+}
+
+#[test]
+fn parse_functions() {
+    // the suffixes belong to the return type
+    assert_eq!(LuaType::parse("fun(): integer[]"), name("fun(): integer[]"));
+    assert_eq!(LuaType::parse("fun(): string?"), name("fun(): string?"));
+    assert_eq!(
+        LuaType::parse("(fun(): integer)[]"),
+        LuaType::Array(Box::new(name("fun(): integer")))
+    );
+
+    // as does a union after the function
+    assert_eq!(LuaType::parse("fun(): a | b"), name("fun(): a | b"));
+    assert_eq!(
+        LuaType::parse("nil | fun(x: a | b): c | d"),
+        LuaType::Union(vec![name("nil"), name("fun(x: a | b): c | d")])
+    );
+    assert_eq!(
+        LuaType::parse("my_fun(a) | b"),
+        LuaType::Union(vec![name("my_fun(a)"), name("b")])
+    );
+    assert_eq!(
+        LuaType::parse("table<string, fun(): a | b>"),
+        LuaType::Table(Box::new(name("string")), Box::new(name("fun(): a | b")))
+    );
+}
+
+#[test]
+fn round_trip() {
+    for ty in [
+        "integer",
+        "string[]?",
+        "(integer | string)[]",
+        "table<string, [integer, boolean]>",
+        "integer | nil",
+    ] {
+        assert_eq!(LuaType::parse(ty).to_string(), ty);
+    }
+}
+
+#[derive(anno_lua::Anno)]
+#[allow(dead_code)]
+struct Callback {
+    #[anno(lua_type_parts("fun(", "name: string, ", "count: integer", "): boolean?"))]
+    on_event: fn(),
+}
+
+#[test]
+fn lua_type_parts_are_joined() {
+    use anno_lua::Anno as _;
+    let anno_lua::Type::Class(class) = Callback::lua_type() else {
+        panic!("expected a class")
+    };
+    assert_eq!(
+        class.fields[0].ty,
+        "fun(name: string, count: integer): boolean?"
+    );
+}
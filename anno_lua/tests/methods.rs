@@ -0,0 +1,62 @@
+#![cfg(feature = "alloc")]
+use anno_lua::{Anno, Method, Type};
+
+#[derive(Anno)]
+#[anno(method("greet", "fun(self): string"))]
+#[anno(method("rename", "fun(self, name: string)"))]
+#[allow(dead_code)]
+struct Greeter {
+    /// Who is greeted
+    #[anno(lua_type = "string")]
+    name: String,
+    #[anno(lua_type = "integer")]
+    count: u32,
+}
+
+// a userdata without any data fields
+#[derive(Anno)]
+#[anno(method("close", "fun(self)"))]
+#[allow(dead_code)]
+struct Handle {
+    #[anno(ignore)]
+    raw: usize,
+}
+
+#[test]
+fn methods() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Greeter>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Handle>(&mut out).unwrap();
+
+    // after the data fields, in declaration order
+    let expected = "\
+---@class Greeter
+--- Who is greeted
+---@field name string
+---@field count integer
+---@field greet fun(self): string
+---@field rename fun(self, name: string)
+Greeter = { }
+
+---@class Handle
+---@field close fun(self)
+Handle = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn methods_are_not_fields() {
+    let Type::Class(class) = Greeter::lua_type() else {
+        panic!("expected a class")
+    };
+    assert_eq!(
+        class.methods,
+        [
+            Method::new("greet", "fun(self): string"),
+            Method::new("rename", "fun(self, name: string)"),
+        ]
+    );
+    assert!(class.field("greet").is_none());
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+use anno_lua::{Alias, Anno, Type};
+
+/// Someone playing
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Player {
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[derive(Anno)]
+#[anno(with_reverse)]
+#[allow(dead_code)]
+enum Team {
+    Red,
+    Blue,
+}
+
+#[test]
+fn generate_module() {
+    let types = [
+        Player::lua_type(),
+        Team::lua_type(),
+        Type::Alias(Alias::new("Score", "integer")),
+        Type::Builtin("string"),
+    ];
+    let mut out = vec![];
+    anno_lua::generate_module(&mut out, " game ", &types).unwrap();
+
+    let expected = "\
+local game = { }
+
+--- Someone playing
+---@class Player
+---@field name string
+game.Player = { }
+
+---@enum Team
+game.Team = {
+    Red = 0,
+    Blue = 1,
+}
+
+---@type table<integer, string>
+game.Team_reverse = {
+    [0] = \"Red\",
+    [1] = \"Blue\",
+}
+
+---@alias Score integer
+
+return game
+";
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+    // the same as the `fmt::Write` version
+    let mut fmt = String::new();
+    anno_lua::generate_module_fmt(&mut fmt, "game", &types).unwrap();
+    assert_eq!(fmt, expected);
+}
+
+#[test]
+fn empty_module() {
+    let mut out = vec![];
+    anno_lua::generate_module(&mut out, "M", &[]).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "local M = { }\n\nreturn M\n"
+    );
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "alloc")]
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(guess)]
+#[allow(dead_code)]
+struct Keywords {
+    r#fn: String,
+    r#type: u32,
+    #[anno(name = "end")]
+    r#loop: bool,
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn raw_identifiers() {
+    let mut out = vec![];
+    anno_lua::generate::<Keywords>(&mut out).unwrap();
+
+    let expected = "\
+---@class Keywords
+---@field fn string
+---@field type integer
+---@field end boolean
+Keywords = { }
+
+";
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+#[derive(Anno)]
+#[allow(dead_code, non_camel_case_types)]
+enum Op {
+    r#In,
+    r#type,
+}
+
+#[test]
+fn raw_variants() {
+    use anno_lua::AnnoEnum as _;
+
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Op>(&mut out).unwrap();
+
+    let expected = "\
+---@enum Op
+Op = {
+    In = 0,
+    type = 1,
+}
+
+";
+    assert_eq!(out, expected);
+    assert_eq!(Op::r#type.variant_name(), "type");
+}
+
+#[derive(Anno)]
+#[anno(name = Vec2, rename_all = camelCase)]
+#[allow(dead_code)]
+struct Point {
+    #[anno(name = x, lua_type = number)]
+    pos_x: f32,
+    #[anno(lua_type = "number")]
+    pos_y: f32,
+}
+
+#[derive(Anno)]
+#[anno(name = Dir)]
+#[allow(dead_code)]
+enum Direction {
+    #[anno(name = up)]
+    Up,
+    #[anno(name = r#type)]
+    Down,
+}
+
+#[test]
+fn bare_idents() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Point>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Direction>(&mut out).unwrap();
+
+    let expected = "\
+---@class Vec2
+---@field x number
+---@field posY number
+Vec2 = { }
+
+---@enum Dir
+Dir = {
+    up = 0,
+    type = 1,
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[anno(name_template = "Lua{}", guess)]
+#[allow(dead_code)]
+struct Node {
+    next: Option<Box<Node>>,
+}
+
+#[derive(Anno)]
+#[anno(name_template = "Lua{}", name = "Shape")]
+#[allow(dead_code)]
+enum Kind {
+    Circle,
+}
+
+#[test]
+fn name_template() {
+    assert_eq!(Node::LUA_NAME, "LuaNode");
+    assert_eq!(Kind::LUA_NAME, "Shape");
+
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Node>(&mut out).unwrap();
+
+    let expected = "\
+---@class LuaNode
+---@field next LuaNode?
+LuaNode = { }
+
+";
+    assert_eq!(out, expected);
+}
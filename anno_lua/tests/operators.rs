@@ -0,0 +1,31 @@
+#![cfg(feature = "alloc")]
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(guess, self_refers_to_class)]
+#[anno(operator("add", "self", "self"), operator("mul", "number", "self"))]
+#[anno(operator("unm", "self"), operator("len", "integer"))]
+#[allow(dead_code)]
+struct Vec2 {
+    x: f32,
+    y: f32,
+}
+
+#[test]
+fn operators() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Vec2>(&mut out).unwrap();
+
+    let expected = "\
+---@class Vec2
+---@operator add(Vec2): Vec2
+---@operator mul(number): Vec2
+---@operator unm: Vec2
+---@operator len: integer
+---@field x number
+---@field y number
+Vec2 = { }
+
+";
+    assert_eq!(out, expected);
+}
@@ -0,0 +1,47 @@
+use anno_lua::{Anno, Type};
+
+mod common;
+use common::field_types;
+
+#[derive(Anno)]
+#[anno(opt_in)]
+#[allow(dead_code)]
+struct Internal {
+    cache: Vec<u8>,
+    #[anno(lua_type = "string")]
+    name: String,
+    dirty: bool,
+    #[anno(include, guess)]
+    count: u32,
+}
+
+#[derive(Anno)]
+#[anno(opt_in, guess)]
+#[allow(dead_code)]
+struct Guessed {
+    cache: Vec<u8>,
+    #[anno(include)]
+    count: u32,
+}
+
+#[test]
+fn only_included_fields() {
+    assert_eq!(
+        field_types::<Internal>(),
+        [("name", "string"), ("count", "integer")]
+    );
+}
+
+#[test]
+fn guess_only_applies_to_included_fields() {
+    assert_eq!(field_types::<Guessed>(), [("count", "integer")]);
+}
+
+#[test]
+fn field_by_name() {
+    let Type::Class(class) = Internal::lua_type() else {
+        panic!("expected a class")
+    };
+    assert_eq!(class.field("name").map(|field| field.ty), Some("string"));
+    assert!(class.field("cache").is_none());
+}
@@ -0,0 +1,277 @@
+#![cfg(feature = "alloc")]
+use anno_lua::{Anno, Discriminant, Enum, GenerateOptions, Type, Variant};
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Point {
+    #[anno(lua_type = "number")]
+    x: f32,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Direction {
+    /// Towards the sky
+    Up,
+    Down,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Nothing {
+    #[anno(ignore)]
+    Never,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Compass {
+    #[anno(lua_type = "\"north\" | \"south\" | \"east\"")]
+    dir: String,
+    #[anno(lua_type = "string | nil")]
+    name: Option<String>,
+}
+
+/// Somewhere to go
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Waypoint {
+    /// From the left
+    #[anno(lua_type = "number")]
+    x: f32,
+}
+
+#[derive(Anno)]
+#[anno(exact)]
+#[allow(dead_code)]
+struct Strict {
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[test]
+fn diagnostics() {
+    // rust rejects duplicate discriminants, so this is built by hand
+    const VARIANTS: &[Variant] = &[
+        Variant::new("Low", Discriminant::Number(0)),
+        Variant::new("Off", Discriminant::Number(0)),
+        Variant::new("High", Discriminant::Number(1)),
+    ];
+    let ty = Type::Enum(Enum::new("Level", VARIANTS));
+
+    let mut options = GenerateOptions::new();
+    options.diagnostics = true;
+    let mut out = String::new();
+    anno_lua::generate_type_fmt_with(&mut out, &ty, &options).unwrap();
+
+    let expected = "\
+---@enum Level
+Level = {
+    Low = 0,
+    Off = 0, -- note: duplicate discriminant
+    High = 1,
+}
+
+";
+    assert_eq!(out, expected);
+
+    let mut out = String::new();
+    anno_lua::generate_type_fmt(&mut out, &ty).unwrap();
+    assert!(!out.contains("note"));
+}
+
+#[test]
+fn emit_exact() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Strict>(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "---@class (exact) Strict\n---@field name string\nStrict = { }\n\n"
+    );
+
+    let options = GenerateOptions {
+        emit_exact: false,
+        ..GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_fmt_with::<Strict>(&mut out, &options).unwrap();
+    assert_eq!(
+        out,
+        "---@class Strict\n---@field name string\nStrict = { }\n\n"
+    );
+
+    // only an exact class gets the marker
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Point>(&mut out).unwrap();
+    assert!(out.starts_with("---@class Point\n"));
+}
+
+#[test]
+fn union_wrap_width() {
+    let options = GenerateOptions {
+        union_wrap_width: Some(16),
+        ..GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_fmt_with::<Compass>(&mut out, &options).unwrap();
+
+    // only the union longer than the width is wrapped
+    let expected = "\
+---@alias Compass_dir
+---| \"north\"
+---| \"south\"
+---| \"east\"
+
+---@class Compass
+---@field dir Compass_dir
+---@field name string | nil
+Compass = { }
+
+";
+    assert_eq!(out, expected);
+
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Compass>(&mut out).unwrap();
+    assert!(out.contains("---@field dir \"north\" | \"south\" | \"east\"\n"));
+}
+
+#[test]
+fn prefixes() {
+    const VARIANTS: &[Variant] = &[
+        Variant::new("Low", Discriminant::Number(0)),
+        Variant::new("Off", Discriminant::Number(0)),
+    ];
+
+    let options = GenerateOptions {
+        diagnostics: true,
+        annotation_prefix: "--!",
+        comment_prefix: "//",
+        ..GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_module_fmt_with(
+        &mut out,
+        "M",
+        &[
+            Waypoint::lua_type(),
+            Type::Enum(Enum::new("Level", VARIANTS)),
+        ],
+        &options,
+    )
+    .unwrap();
+
+    let expected = "\
+local M = { }
+
+--! Somewhere to go
+--!@class Waypoint
+--! From the left
+--!@field x number
+M.Waypoint = { }
+
+--!@enum Level
+M.Level = {
+    Low = 0,
+    Off = 0, // note: duplicate discriminant
+}
+
+return M";
+    assert_eq!(out.trim_end(), expected);
+}
+
+#[test]
+fn empty_table() {
+    let options = GenerateOptions {
+        empty_table: "{}",
+        ..GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_module_fmt_with(
+        &mut out,
+        "M",
+        &[Point::lua_type(), Nothing::lua_type()],
+        &options,
+    )
+    .unwrap();
+
+    let expected = "\
+local M = {}
+
+---@class Point
+---@field x number
+M.Point = {}
+
+---@enum Nothing
+M.Nothing = {}
+
+return M";
+    assert_eq!(out.trim_end(), expected);
+}
+
+#[test]
+fn table_indent() {
+    let options = GenerateOptions {
+        table_indent: "\t",
+        ..GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_fmt_with::<Direction>(&mut out, &options).unwrap();
+
+    let expected = "\
+---@enum Direction
+Direction = {
+\t--- Towards the sky
+\tUp = 0,
+\tDown = 1,
+}
+
+";
+    assert_eq!(out, expected);
+}
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Request {
+    #[anno(lua_type = "string?")]
+    body: Option<String>,
+    #[anno(lua_type = "string")]
+    url: String,
+    #[anno(lua_type = "integer", optional_key)]
+    timeout: Option<u32>,
+    #[anno(lua_type = "string")]
+    method: String,
+}
+
+#[test]
+fn required_first() {
+    let options = GenerateOptions {
+        required_first: true,
+        ..GenerateOptions::new()
+    };
+    let mut out = String::new();
+    anno_lua::generate_fmt_with::<Request>(&mut out, &options).unwrap();
+
+    // each group keeps its declaration order
+    let expected = "\
+---@class Request
+---@field url string
+---@field method string
+---@field body string?
+---@field timeout? integer
+Request = { }
+
+";
+    assert_eq!(out, expected);
+
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Request>(&mut out).unwrap();
+    let expected = "\
+---@class Request
+---@field body string?
+---@field url string
+---@field timeout? integer
+---@field method string
+Request = { }
+
+";
+    assert_eq!(out, expected);
+}
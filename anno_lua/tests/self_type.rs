@@ -0,0 +1,47 @@
+#![cfg(feature = "alloc")]
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(guess, self_refers_to_class, method("clone", "fun(self): self"))]
+#[anno(method("lerp", "fun(self, other: self, t: number): self"))]
+#[allow(dead_code)]
+struct Vec2 {
+    x: f32,
+    y: f32,
+    #[anno(lua_type = "self?")]
+    parent: Option<Box<Vec2>>,
+    #[anno(lua_type = "{ self: self, other: self[] }")]
+    pair: (),
+}
+
+#[derive(Anno)]
+#[anno(guess, method("clone", "fun(self): self"))]
+#[allow(dead_code)]
+struct Point {
+    x: f32,
+}
+
+#[test]
+fn self_refers_to_class() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Vec2>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Point>(&mut out).unwrap();
+
+    let expected = "\
+---@class Vec2
+---@field x number
+---@field y number
+---@field parent Vec2?
+---@field pair { self: Vec2, other: Vec2[] }
+---@field clone fun(self): Vec2
+---@field lerp fun(self, other: Vec2, t: number): Vec2
+Vec2 = { }
+
+---@class Point
+---@field x number
+---@field clone fun(self): self
+Point = { }
+
+";
+    assert_eq!(out, expected);
+}
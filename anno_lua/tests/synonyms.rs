@@ -0,0 +1,84 @@
+#![cfg(feature = "alloc")]
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    a: i32,
+    #[anno(skip)]
+    b: i32,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Bar {
+    A,
+    #[anno(skip)]
+    B(i32),
+    C,
+}
+
+#[test]
+fn skip() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Foo>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Bar>(&mut out).unwrap();
+
+    let expected = "\
+---@class Foo
+---@field a integer
+Foo = { }
+
+---@enum Bar
+Bar = {
+    A = 0,
+    C = 2,
+}
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[anno(rename = "Renamed")]
+#[allow(dead_code)]
+struct Baz {
+    #[anno(rename = "value", lua_type = "integer")]
+    a: i32,
+}
+
+#[derive(Anno)]
+#[anno(rename = "Dir")]
+#[allow(dead_code)]
+enum Direction {
+    #[anno(rename = "up")]
+    Up,
+}
+
+#[anno_lua::anno(alias, rename = "Id", lua_type = "integer")]
+#[allow(dead_code)]
+type UserId = u64;
+
+#[test]
+fn rename() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Baz>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Direction>(&mut out).unwrap();
+    anno_lua::generate_fmt::<UserIdAnno>(&mut out).unwrap();
+
+    let expected = "\
+---@class Renamed
+---@field value integer
+Renamed = { }
+
+---@enum Dir
+Dir = {
+    up = 0,
+}
+
+---@alias Id integer
+
+";
+    assert_eq!(out, expected);
+}
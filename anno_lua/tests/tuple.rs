@@ -0,0 +1,148 @@
+#![cfg(feature = "alloc")]
+use std::marker::PhantomData;
+
+use anno_lua::{Anno, Type};
+
+mod common;
+use common::alias;
+
+/// A point on a plane
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Point(f64, f64);
+
+#[derive(Anno)]
+#[anno(name = "Entry")]
+#[allow(dead_code)]
+struct Entry(String, #[anno(lua_type = "{ty}?")] u32, Vec<bool>);
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Id(u64);
+
+#[test]
+fn two_fields() {
+    assert_eq!(alias::<Point>(), ("Point", "[number, number]"));
+
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Point>(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "--- A point on a plane\n---@alias Point [number, number]\n\n"
+    );
+}
+
+#[test]
+fn three_fields() {
+    assert_eq!(alias::<Entry>(), ("Entry", "[string, integer?, boolean[]]"));
+}
+
+#[test]
+fn single_field_is_transparent() {
+    assert_eq!(alias::<Id>(), ("Id", "integer"));
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Handle<T>(u32, PhantomData<T>);
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Range<T>(PhantomData<T>, f32, f32);
+
+#[derive(Anno)]
+#[anno(transparent)]
+#[allow(dead_code)]
+struct Tagged<T> {
+    value: String,
+    tag: PhantomData<T>,
+}
+
+#[test]
+fn phantom_data_is_skipped() {
+    assert_eq!(alias::<Handle<bool>>(), ("Handle", "integer"));
+    assert_eq!(alias::<Range<bool>>(), ("Range", "[number, number]"));
+    assert_eq!(alias::<Tagged<bool>>(), ("Tagged", "string"));
+}
+
+#[test]
+fn lua_name_const() {
+    assert_eq!(Entry::LUA_NAME, "Entry");
+    assert_eq!(Point::LUA_NAME, Point::lua_type().name());
+}
+
+/// A position
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Vec2(#[anno(name = "x")] f32, #[anno(rename = "y")] f32);
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Labeled(
+    #[anno(name = "label")] String,
+    Option<u32>,
+    #[anno(lua_type = "string[]")] Vec<String>,
+);
+
+#[test]
+fn named_fields_are_a_class() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Vec2>(&mut out).unwrap();
+    anno_lua::generate_fmt::<Labeled>(&mut out).unwrap();
+
+    let expected = "\
+--- A position
+---@class Vec2
+---@field x number
+---@field y number
+Vec2 = { }
+
+---@class Labeled
+---@field label string
+---@field [2] integer?
+---@field [3] string[]
+Labeled = { }
+
+";
+    assert_eq!(out, expected);
+}
+
+#[derive(Anno)]
+#[anno(exact)]
+#[allow(dead_code)]
+struct Pair(#[anno(name = "key")] String, u32);
+
+#[test]
+fn positional_fields_can_be_exact() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Pair>(&mut out).unwrap();
+
+    let expected = "\
+---@class (exact) Pair
+---@field key string
+---@field [2] integer
+Pair = { }
+
+";
+    assert_eq!(out, expected);
+
+    // the key isn't an index signature, so it isn't checked as a type
+    let Type::Class(class) = Pair::lua_type() else {
+        panic!("expected a class")
+    };
+    let field = class.field("2").unwrap();
+    assert!(field.positional && !field.index);
+    assert!(anno_lua::validate_types(&[Pair::lua_type()], &[]).is_ok());
+}
+
+#[derive(Anno)]
+#[anno(allow_empty)]
+#[allow(dead_code)]
+struct Marker<T>(PhantomData<T>);
+
+#[test]
+fn only_phantom_data_is_an_empty_class() {
+    let mut out = String::new();
+    anno_lua::generate_fmt::<Marker<bool>>(&mut out).unwrap();
+    assert_eq!(out, "---@class Marker<T>\nMarker = { }\n\n");
+}
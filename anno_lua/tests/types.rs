@@ -0,0 +1,148 @@
+use anno_lua::{Alias, Anno, Class, Discriminant, Enum, Field, Type, ValueAlias, Variant};
+
+#[derive(Anno)]
+#[anno(exact)]
+#[allow(dead_code)]
+struct Strict {
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Loose {
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[test]
+fn is_exact() {
+    assert!(Strict::lua_type().is_exact());
+    assert!(!Loose::lua_type().is_exact());
+
+    // only a class can be exact
+    assert!(!Side::lua_type().is_exact());
+    assert!(!Type::Alias(Alias::new("Id", "integer")).is_exact());
+    assert!(!Type::Builtin("string").is_exact());
+}
+
+// it can be used in a const context
+const _: () = assert!(Type::Class(Class {
+    exact: true,
+    ..Class::new("Const", &[])
+})
+.is_exact());
+
+// a hand written impl, e.g. for a type from another crate
+struct Remote;
+
+impl Anno for Remote {
+    fn lua_type() -> Type {
+        const FIELDS: &[Field] = &[Field::new("name", "string")];
+        Type::Class(Class::new("Loose", FIELDS))
+    }
+}
+
+#[test]
+fn constructors() {
+    // the same as what the derive produces
+    assert_eq!(Remote::lua_type(), Loose::lua_type());
+
+    const VARIANTS: &[Variant] = &[
+        Variant::new("Left", Discriminant::Number(0)),
+        Variant::new("Right", Discriminant::Number(1)),
+    ];
+    assert_eq!(Type::Enum(Enum::new("Side", VARIANTS)), Side::lua_type());
+
+    let field = Field::new("id", "integer");
+    assert!(field.docs.is_empty());
+    assert_eq!(field.location, None);
+    assert!(!field.optional_key);
+    assert!(!field.index);
+    assert_eq!(field.cast, None);
+    assert!(field.inline.is_none());
+    assert!(Variant::new("Up", Discriminant::Number(0)).docs.is_empty());
+    assert!(Alias::new("Id", "integer").docs.is_empty());
+
+    let class = Class::new("Empty", &[]);
+    assert!(!class.exact && class.docs.is_empty() && class.generics.is_empty());
+    assert!(class.methods.is_empty());
+    assert!(class.headers.is_empty());
+    assert!(!class.self_refers_to_class);
+    assert!(class.operators.is_empty());
+
+    let enum_ = Enum::new("Empty", &[]);
+    assert!(enum_.docs.is_empty());
+    assert_eq!(enum_.value_alias, ValueAlias::None);
+    assert!(!enum_.reverse);
+    assert!(enum_.headers.is_empty());
+}
+
+#[derive(Anno)]
+#[anno(self)]
+#[allow(dead_code)]
+enum Named {
+    First,
+    Second,
+}
+
+#[derive(Anno)]
+#[anno(alias = "Other")]
+#[allow(dead_code)]
+enum Aliased {
+    One,
+}
+
+#[test]
+fn is_self_referential() {
+    let self_referential = |ty: Type| match ty {
+        Type::Enum(enum_) => enum_.is_self_referential(),
+        _ => panic!("expected an enum"),
+    };
+    assert!(self_referential(Named::lua_type()));
+    assert!(self_referential(Aliased::lua_type()));
+    assert!(!self_referential(Side::lua_type()));
+    assert!(!self_referential(Signed::lua_type()));
+
+    // a single named variant is enough
+    const VARIANTS: &[Variant] = &[
+        Variant::new("Plain", Discriminant::Number(0)),
+        Variant::new("Named", Discriminant::Named("Mixed")),
+    ];
+    assert!(Enum::new("Mixed", VARIANTS).is_self_referential());
+    assert!(!Enum::new("Empty", &[]).is_self_referential());
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+enum Signed {
+    Negative = -3,
+    Zero = 0,
+    #[anno(value = "big")]
+    Big,
+}
+
+#[test]
+fn numeric_value() {
+    let Type::Enum(signed) = Signed::lua_type() else {
+        panic!("expected an enum")
+    };
+    let values = signed
+        .variants
+        .iter()
+        .map(Variant::numeric_value)
+        .collect::<Vec<_>>();
+    assert_eq!(values, [Some(-3), Some(0), None]);
+
+    assert_eq!(
+        Variant::new("Named", Discriminant::Named("Side")).numeric_value(),
+        None
+    );
+}
@@ -0,0 +1,10 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+#[anno(alias = "Bar")]
+enum Foo {
+    A,
+    B = 2,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use anno_lua::anno;
+
+#[anno(alias, name = "")]
+type Name = String;
+
+#[anno(alias, lua_type = " ")]
+type Type = String;
+
+#[anno(alias, doc = "")]
+type Doc = String;
+
+#[anno(alias, doc_replace = "")]
+type DocReplace = String;
+
+#[anno(alias, since = "")]
+type Since = String;
+
+fn main() {}
@@ -0,0 +1,21 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(value_alias = "AValue", as_alias)]
+enum A {
+    One,
+}
+
+#[derive(Anno)]
+#[anno(as_alias, as_literal_alias)]
+enum B {
+    One,
+}
+
+#[derive(Anno)]
+#[anno(as_literal_alias, value_alias = "CValue")]
+enum C {
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use anno_lua::anno;
+
+#[anno(alias)]
+type Pair<T> = (T, T);
+
+#[anno(alias, lua_type = "integer[]")]
+type Buffer<const N: usize> = [u8; N];
+
+// a lifetime is fine
+#[anno(alias, lua_type = "string")]
+type Name<'a> = &'a str;
+
+fn main() {}
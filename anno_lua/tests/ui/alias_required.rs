@@ -0,0 +1,8 @@
+use anno_lua::anno;
+
+struct Foo;
+
+#[anno(name = "Bar")]
+type Bar = Foo;
+
+fn main() {}
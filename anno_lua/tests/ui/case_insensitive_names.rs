@@ -0,0 +1,21 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(case_insensitive_names)]
+struct Foo {
+    #[anno(lua_type = "integer", name = "fooBar")]
+    a: i32,
+    #[anno(lua_type = "integer", name = "foobar")]
+    b: i32,
+}
+
+#[derive(Anno)]
+#[anno(case_insensitive_names)]
+enum Bar {
+    #[anno(name = "Up")]
+    A,
+    #[anno(name = "UP")]
+    B,
+}
+
+fn main() {}
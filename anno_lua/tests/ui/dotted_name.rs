@@ -0,0 +1,15 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Foo {
+    #[anno(lua_type = "integer", name = "config.timeout")]
+    timeout: i32,
+}
+
+#[derive(Anno, Copy, Clone)]
+enum Bar {
+    #[anno(name = "a.b")]
+    A,
+}
+
+fn main() {}
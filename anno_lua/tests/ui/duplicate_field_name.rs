@@ -0,0 +1,11 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    a: i32,
+    #[anno(lua_type = "integer", name = "a")]
+    b: i32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+enum Foo {
+    A,
+    #[anno(name = "A")]
+    B,
+}
+
+fn main() {}
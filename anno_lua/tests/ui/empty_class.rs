@@ -0,0 +1,26 @@
+use std::marker::PhantomData;
+
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Unit;
+
+#[derive(Anno)]
+#[anno(guess)]
+struct Ignored {
+    #[anno(ignore)]
+    a: i32,
+}
+
+#[derive(Anno)]
+struct Marker<T>(PhantomData<T>);
+
+#[derive(Anno)]
+#[anno(allow_empty)]
+struct Allowed;
+
+#[derive(Anno)]
+#[anno(method("greet", "fun(self)"))]
+struct Methods {}
+
+fn main() {}
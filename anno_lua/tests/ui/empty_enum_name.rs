@@ -0,0 +1,9 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(name = "")]
+enum Foo {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Foo {
+    #[anno(lua_type = "integer", name = "")]
+    a: i32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(name = "  ")]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    a: i32,
+}
+
+fn main() {}
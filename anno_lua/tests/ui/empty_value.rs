@@ -0,0 +1,28 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(alias = " ")]
+enum A {
+    One,
+}
+
+#[derive(Anno)]
+#[anno(value_alias = "")]
+enum B {
+    One,
+}
+
+#[derive(Anno)]
+#[anno(header = "")]
+enum C {
+    One,
+}
+
+#[derive(Anno)]
+#[anno(header = "")]
+struct D {
+    #[anno(lua_type = "integer")]
+    one: i32,
+}
+
+fn main() {}
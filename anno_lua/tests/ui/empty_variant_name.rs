@@ -0,0 +1,9 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+enum Foo {
+    #[anno(name = "")]
+    A,
+}
+
+fn main() {}
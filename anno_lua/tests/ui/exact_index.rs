@@ -0,0 +1,15 @@
+use std::collections::HashMap;
+
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(exact)]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    a: i32,
+    #[anno(flatten)]
+    #[anno(lua_type = "integer")]
+    rest: HashMap<String, i32>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use anno_lua::Anno;
+
+const B: isize = 2;
+
+#[derive(Anno, Copy, Clone)]
+enum Foo {
+    A = 1,
+    B = B,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Foo {
+    #[anno(flatten)]
+    extra: Option<String>,
+}
+
+fn main() {}
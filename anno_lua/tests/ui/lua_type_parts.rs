@@ -0,0 +1,11 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Foo {
+    #[anno(lua_type = "integer", lua_type_parts("fun(", ")"))]
+    a: i32,
+    #[anno(lua_type_parts())]
+    b: i32,
+}
+
+fn main() {}
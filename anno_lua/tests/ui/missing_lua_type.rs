@@ -0,0 +1,8 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Foo {
+    a: i32,
+}
+
+fn main() {}
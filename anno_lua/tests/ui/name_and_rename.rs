@@ -0,0 +1,16 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(name = "Foo", rename = "Bar")]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    a: i32,
+}
+
+#[derive(Anno)]
+struct Baz {
+    #[anno(name = "a", rename = "b", lua_type = "integer")]
+    a: i32,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(name_template = "Lua")]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    a: i32,
+}
+
+#[derive(Anno)]
+#[anno(name_template = "Lua")]
+enum Bar {
+    A,
+}
+
+fn main() {}
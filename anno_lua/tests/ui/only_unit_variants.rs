@@ -0,0 +1,9 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+enum Foo {
+    A,
+    B(i32),
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(operator("plus", "Foo", "Foo"))]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    a: i32,
+}
+
+#[derive(Anno)]
+#[anno(operator("add", "Bar"))]
+struct Bar {
+    #[anno(lua_type = "integer")]
+    a: i32,
+}
+
+#[derive(Anno)]
+#[anno(operator("unm", "Baz", "Baz"))]
+struct Baz {
+    #[anno(lua_type = "integer")]
+    a: i32,
+}
+
+fn main() {}
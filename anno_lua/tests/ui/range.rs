@@ -0,0 +1,16 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(guess)]
+struct Foo {
+    #[anno(range = "0..=255")]
+    a: u8,
+    #[anno(range = "-1..")]
+    b: i32,
+    #[anno(range = "small")]
+    c: i32,
+    #[anno(range = "..")]
+    d: i32,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(guess, rename_all = "camelCase")]
+struct Foo {
+    #[anno(name = "fooBar")]
+    a: i32,
+    foo_bar: i32,
+}
+
+#[derive(Anno)]
+#[anno(guess, rename_all = "camelCase")]
+struct Bar {
+    foo_bar: i32,
+    #[anno(name = "fooBar")]
+    b: i32,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(name = "Dir")]
+enum Direction {
+    Up,
+    Down,
+}
+
+#[derive(Anno)]
+#[anno(guess)]
+struct Player {
+    #[anno(reference)]
+    facing: Option<Direction>,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+#[anno(self, alias = "Bar")]
+enum Foo {
+    A,
+    B,
+}
+
+#[derive(Anno, Copy, Clone)]
+#[anno(alias = "Bar")]
+#[anno(self)]
+enum Baz {
+    A,
+    B,
+}
+
+fn main() {}
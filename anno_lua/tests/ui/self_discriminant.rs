@@ -0,0 +1,10 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+#[anno(self)]
+enum Foo {
+    A = 1,
+    B,
+}
+
+fn main() {}
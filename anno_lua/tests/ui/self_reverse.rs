@@ -0,0 +1,18 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+#[anno(self)]
+#[anno(with_reverse)]
+enum Foo {
+    A,
+    B,
+}
+
+#[derive(Anno, Copy, Clone)]
+#[anno(with_reverse, alias = "Bar")]
+enum Bar {
+    A,
+    B,
+}
+
+fn main() {}
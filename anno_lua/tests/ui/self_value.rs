@@ -0,0 +1,11 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+#[anno(self)]
+enum Foo {
+    #[anno(value = "a")]
+    A,
+    B,
+}
+
+fn main() {}
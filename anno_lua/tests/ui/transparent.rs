@@ -0,0 +1,7 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(transparent)]
+struct Foo(i32, i32);
+
+fn main() {}
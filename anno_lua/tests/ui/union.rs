@@ -0,0 +1,9 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+union Foo {
+    a: u32,
+    b: f32,
+}
+
+fn main() {}
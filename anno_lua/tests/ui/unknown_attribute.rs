@@ -0,0 +1,13 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+struct Foo {
+    #[anno(lua_type = "integer", nullable)]
+    a: i32,
+    #[anno(lua_type = "integer", default_value = 42)]
+    b: i32,
+    #[anno(lua_type = "integer", values("a", "b"))]
+    c: i32,
+}
+
+fn main() {}
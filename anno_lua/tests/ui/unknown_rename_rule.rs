@@ -0,0 +1,10 @@
+use anno_lua::Anno;
+
+#[derive(Anno)]
+#[anno(rename_all = "kebab-case")]
+struct Foo {
+    #[anno(lua_type = "integer")]
+    some_field: i32,
+}
+
+fn main() {}
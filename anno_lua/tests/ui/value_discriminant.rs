@@ -0,0 +1,9 @@
+use anno_lua::Anno;
+
+#[derive(Anno, Copy, Clone)]
+enum Foo {
+    #[anno(value = "a")]
+    A = 1,
+}
+
+fn main() {}
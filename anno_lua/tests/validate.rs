@@ -0,0 +1,151 @@
+#![cfg(feature = "alloc")]
+use anno_lua::{Anno, GenerateOptions, UnknownType};
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct User {
+    #[anno(lua_type = "string")]
+    name: String,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Widget {
+    #[anno(lua_type = "User?")]
+    owner: Option<User>,
+    #[anno(lua_type = "table<string, User[]>")]
+    groups: (),
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Typo {
+    #[anno(lua_type = "Usr | nil")]
+    owner: Option<User>,
+}
+
+#[test]
+fn known_references() {
+    anno_lua::validate_all(&[User::lua_type(), Widget::lua_type()]).unwrap();
+}
+
+#[test]
+fn unknown_references() {
+    let errors = anno_lua::validate_all(&[User::lua_type(), Typo::lua_type()]).unwrap_err();
+    assert_eq!(errors, ["`Typo.owner` references an unknown type `Usr`"]);
+
+    // `User` is only generated elsewhere
+    let errors = anno_lua::validate_all(&[Widget::lua_type()]).unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            "`Widget.owner` references an unknown type `User`",
+            "`Widget.groups` references an unknown type `User`",
+        ]
+    );
+}
+
+#[test]
+fn strict_refs() {
+    let options = GenerateOptions {
+        strict_refs: true,
+        ..GenerateOptions::default()
+    };
+
+    let mut out = String::new();
+    anno_lua::generate_module_fmt_with(&mut out, "M", &[Typo::lua_type()], &options).unwrap_err();
+    assert!(out.is_empty());
+
+    #[cfg(feature = "std")]
+    {
+        let mut out = vec![];
+        let err = anno_lua::generate_module_with(&mut out, "M", &[Typo::lua_type()], &options)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(out.is_empty());
+    }
+
+    let mut out = String::new();
+    anno_lua::generate_module_fmt_with(
+        &mut out,
+        "M",
+        &[User::lua_type(), Widget::lua_type()],
+        &options,
+    )
+    .unwrap();
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Player {
+    #[anno(lua_type = "vec3")]
+    position: (),
+    #[anno(lua_type = "fun(self, target: Player): boolean")]
+    attack: (),
+}
+
+#[test]
+fn allowed_names() {
+    let errors = anno_lua::validate_types(&[Player::lua_type()], &[]).unwrap_err();
+    assert_eq!(
+        errors,
+        [UnknownType {
+            ty: "Player",
+            field: Some("position"),
+            name: "vec3".to_string(),
+        }]
+    );
+
+    anno_lua::validate_types(&[Player::lua_type()], &["vec3"]).unwrap();
+}
+
+#[derive(Anno)]
+#[anno(
+    method("attack", "fun(self, target: Enemy, times: integer): boolean"),
+    method("spawn", "fun(at: vec3): Player, Usr?"),
+    operator("add", "Vec2", "Player"),
+    operator("unm", "Unknown")
+)]
+#[allow(dead_code)]
+struct Signatures {
+    #[anno(lua_type = "fun(other: Player): ok: boolean")]
+    compare: (),
+}
+
+#[test]
+fn signatures() {
+    let errors = anno_lua::validate_types(&[Signatures::lua_type(), Player::lua_type()], &["vec3"])
+        .unwrap_err();
+    let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            "`Signatures.attack` references an unknown type `Enemy`",
+            "`Signatures.spawn` references an unknown type `Usr`",
+            "`Signatures.add` references an unknown type `Vec2`",
+            "`Signatures.unm` references an unknown type `Unknown`",
+        ]
+    );
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Position {
+    #[anno(lua_type = "number")]
+    x: f32,
+}
+
+#[derive(Anno)]
+#[allow(dead_code)]
+struct Sprite {
+    // only used if `Position` turns out to not be a class
+    #[anno(inline, lua_type = "Missing")]
+    position: Position,
+    #[anno(lua_type = "number")]
+    y: f32,
+}
+
+#[test]
+fn inlined_fields() {
+    anno_lua::validate_types(&[Sprite::lua_type()], &[]).unwrap();
+}
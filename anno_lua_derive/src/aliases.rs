@@ -0,0 +1,168 @@
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt as _, meta::ParseNestedMeta, spanned::Spanned, ItemType};
+
+use crate::{
+    attrs::parse_value,
+    data,
+    docs::{collect_docs, override_docs, since_docs},
+    error::Error,
+    guess::{guess_type, guessed_references, referenced_types, GuessContext},
+};
+
+#[derive(Default)]
+struct AliasMeta {
+    alias: bool,
+    reference: bool,
+    name: Option<String>,
+    marker: Option<syn::Ident>,
+    lua_type: Option<String>,
+    doc: Option<String>,
+    doc_replace: Option<String>,
+    since: Option<String>,
+}
+
+impl AliasMeta {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("alias") {
+            self.alias = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("reference") {
+            self.reference = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("marker") {
+            let lit = parse_value(meta.value()?)?;
+            self.marker = Some(lit.parse()?);
+            return Ok(());
+        }
+
+        let (key, value) = if meta.path.is_ident("name") || meta.path.is_ident("rename") {
+            if self.name.is_some() {
+                return Err(Error::DuplicateName(meta.path.span()).into_syn_error());
+            }
+            ("name", &mut self.name)
+        } else if meta.path.is_ident("lua_type") {
+            ("lua_type", &mut self.lua_type)
+        } else if meta.path.is_ident("doc") {
+            ("doc", &mut self.doc)
+        } else if meta.path.is_ident("doc_replace") {
+            ("doc_replace", &mut self.doc_replace)
+        } else if meta.path.is_ident("since") {
+            ("since", &mut self.since)
+        } else {
+            return Err(meta.error("unknown attribute"));
+        };
+
+        let lit = meta.value()?;
+        let span = lit.span();
+        let data = parse_value(lit)?.value();
+        if data.trim().is_empty() {
+            let err = match key {
+                "name" => Error::EmptyName(span),
+                key => Error::EmptyValue(span, key),
+            };
+            return Err(err.into_syn_error());
+        }
+        *value = Some(data);
+        Ok(())
+    }
+}
+
+pub fn parse(attr: proc_macro::TokenStream, item: &ItemType) -> proc_macro::TokenStream {
+    let mut meta = AliasMeta::default();
+    let parser = syn::meta::parser(|m| meta.parse(m));
+    if let Err(err) = syn::parse::Parser::parse(parser, attr) {
+        return error_with_item(err, item);
+    }
+
+    if !meta.alias {
+        return error_with_item(
+            Error::AliasRequired(item.ident.span()).into_syn_error(),
+            item,
+        );
+    }
+
+    // lifetimes don't matter to lua, but the type parameters would be lost on the marker
+    if let Some(param) = item
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, syn::GenericParam::Lifetime(..)))
+    {
+        return error_with_item(Error::AliasGenerics(param.span()).into_syn_error(), item);
+    }
+
+    let docs = since_docs(
+        override_docs(collect_docs(&item.attrs), meta.doc, meta.doc_replace),
+        meta.since,
+    );
+    let name = meta.name.unwrap_or_else(|| item.ident.unraw().to_string());
+    let cx = GuessContext {
+        references: meta.reference,
+        generics: Some(&item.generics),
+        ..GuessContext::default()
+    };
+    let mut references = vec![];
+    let mut guessed = vec![];
+    if meta.reference {
+        referenced_types(&item.ty, &mut references);
+        if meta.lua_type.is_none() {
+            guessed = guessed_references(&item.ty, cx);
+        }
+    }
+    let (checks, inline_checks) = data::check_guessed(&guessed, &item.generics);
+    let dependencies = data::dependencies(&references.iter().collect::<Vec<_>>(), &[]);
+    let ty = meta.lua_type.unwrap_or_else(|| guess_type(&item.ty, cx));
+
+    // an impl on the alias would be on the aliased type, which is usually a foreign type
+    // or already implements `Anno`. so the annotations are on a marker type instead
+    let marker = meta
+        .marker
+        .unwrap_or_else(|| format_ident!("{}Anno", item.ident.unraw(), span = item.ident.span()));
+    let vis = &item.vis;
+    let ident = &item.ident;
+    let doc = format!(" The lua annotations for the [`{ident}`] alias");
+    let ast = quote! {
+        #item
+
+        #[doc = #doc]
+        #[derive(Copy, Clone, Debug, Default)]
+        #[allow(dead_code)]
+        #vis struct #marker;
+
+        impl anno_lua::Anno for #marker {
+            fn lua_type() -> anno_lua::Type {
+                #inline_checks
+                anno_lua::Type::Alias(anno_lua::Alias {
+                    docs: &[ #( #docs ),* ],
+                    name: #name,
+                    ty: #ty,
+                })
+            }
+
+            #dependencies
+        }
+
+        impl #marker {
+            /// The name of this type in lua
+            pub const LUA_NAME: &'static str = #name;
+        }
+
+        #checks
+    };
+
+    ast.into()
+}
+
+// the item is kept so that a bad attribute doesn't also cause errors at every use of the alias
+fn error_with_item(err: syn::Error, item: &ItemType) -> proc_macro::TokenStream {
+    let err = err.into_compile_error();
+    quote! {
+        #item
+        #err
+    }
+    .into()
+}
@@ -1,13 +1,58 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
 use proc_macro2::Span;
-use syn::{spanned::Spanned, Attribute, LitStr};
+use syn::{
+    ext::IdentExt as _, parse::ParseStream, punctuated::Punctuated, spanned::Spanned, Attribute,
+    LitStr, Token,
+};
+
+use crate::error::Error;
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub enum Kind {
     Type,
+    TypeParts,
     Name,
     Ignore,
+    Include,
+    Value,
+    Doc,
+    DocReplace,
+    Guess,
+    NoGuess,
+    OptionalKey,
+    Flatten,
+    Inline,
+    Reference,
+    Since,
+    Cast,
+    Default,
+    Range,
+}
+
+impl Kind {
+    /// Flags don't take a value, e.g. `#[anno(ignore)]`
+    const fn is_flag(self) -> bool {
+        matches!(
+            self,
+            Self::Ignore
+                | Self::Include
+                | Self::Guess
+                | Self::NoGuess
+                | Self::OptionalKey
+                | Self::Flatten
+                | Self::Inline
+                | Self::Reference
+        )
+    }
+
+    /// Lists are joined into the value of another kind, e.g. `#[anno(lua_type_parts("fun(", "): string"))]`
+    const fn joined(self) -> Option<Self> {
+        match self {
+            Self::TypeParts => Some(Self::Type),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -20,81 +65,96 @@ pub struct Attr {
 pub fn parse_attrs(
     attrs: &[Attribute],
     allowed: &[(&'static str, Kind)],
-) -> Result<HashMap<Kind, Attr>, syn::Error> {
+) -> Result<BTreeMap<Kind, Attr>, syn::Error> {
     let map: BTreeMap<&'static str, Kind> = allowed.iter().copied().collect();
 
-    let Some(attr) = attrs.iter().find(|c| c.path().is_ident("anno")) else {
-        return Ok(HashMap::new());
-    };
-
     let mut errors = vec![];
-    let mut out = HashMap::new();
-
-    attr.meta.require_list()?.parse_nested_meta(|meta| {
-        let path = &meta.path;
-
-        if let Some(id) = path.get_ident() {
-            if map.get(&*id.to_string()) == Some(&Kind::Ignore) {
-                let attr = Attr {
-                    key: meta.path.span(),
-                    value: meta.path.span(),
-                    data: String::new(),
-                };
-                out.insert(Kind::Ignore, attr);
-                return Ok(());
+    // ordered, so anything iterating over the attributes reports them the same way on every build
+    let mut out = BTreeMap::new();
+
+    // there can be several of these, e.g. when some come from a `cfg_attr`
+    for attr in attrs.iter().filter(|c| c.path().is_ident("anno")) {
+        attr.meta.require_list()?.parse_nested_meta(|meta| {
+            let path = &meta.path;
+
+            if let Some(id) = path.get_ident() {
+                if let Some(&kind) = map.get(&*id.to_string()).filter(|kind| kind.is_flag()) {
+                    let attr = Attr {
+                        key: meta.path.span(),
+                        value: meta.path.span(),
+                        data: String::new(),
+                    };
+                    out.insert(kind, attr);
+                    return Ok(());
+                }
             }
-        }
 
-        let ident = path.require_ident()?;
-        let raw = ident.to_string();
-
-        let kind = map.get(&*raw).ok_or_else(|| {
-            let available = map.keys().fold(String::new(), |mut a, c| {
-                if !a.is_empty() {
-                    a.push_str(", ");
-                }
-                a.push_str(c);
-                a
+            let ident = path.require_ident()?;
+            let raw = ident.to_string();
+
+            let kind = map.get(&*raw).ok_or_else(|| {
+                let available = map.keys().fold(String::new(), |mut a, c| {
+                    if !a.is_empty() {
+                        a.push_str(", ");
+                    }
+                    a.push_str(c);
+                    a
+                });
+
+                syn::Error::new(
+                    path.span(),
+                    format!("unknown ident: {raw}, supported: {available}",),
+                )
             });
 
-            syn::Error::new(
-                path.span(),
-                format!("unknown ident: {raw}, supported: {available}",),
-            )
-        });
+            let kind = match kind {
+                Ok(kind) => *kind,
+                Err(err) => {
+                    skip_value(&meta)?;
+                    errors.push(err);
+                    return Ok(());
+                }
+            };
+
+            let (kind, value_span, value) = match kind.joined() {
+                Some(joined) => {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let value_span = content.span();
+                    let parts = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                    let value = parts.iter().map(LitStr::value).collect::<String>();
+                    (joined, value_span, value)
+                }
+                None => {
+                    let value = meta.value()?;
+                    let value_span = value.span();
+                    (kind, value_span, parse_value(value)?.value())
+                }
+            };
 
-        let kind = match kind {
-            Ok(kind) => *kind,
-            Err(err) => {
-                let _ = meta.value()?.parse::<LitStr>()?;
-                errors.push(err);
+            if value.trim().is_empty() {
+                errors.push(match kind {
+                    Kind::Name => Error::EmptyName(value_span).into_syn_error(),
+                    _ => syn::Error::new(value_span, "attribute cannot be empty"),
+                });
                 return Ok(());
             }
-        };
 
-        let value = meta.value()?;
-        let value_span = value.span();
-        let value = value.parse::<LitStr>()?.value();
-
-        if value.trim().is_empty() {
-            errors.push(syn::Error::new(value_span, "attribute cannot be empty"));
-            return Ok(());
-        }
-
-        if let Some(Attr { key: previous, .. }) = out.insert(
-            kind,
-            Attr {
-                key: meta.path.span(),
-                value: value_span,
-                data: value,
-            },
-        ) {
-            let mut err = syn::Error::new(path.span(), "duplicate attribute found");
-            err.combine(syn::Error::new(previous, "previous use here"));
-            errors.push(err);
-        }
-        Ok(())
-    })?;
+            if let Some(Attr { key: previous, .. }) = out.insert(
+                kind,
+                Attr {
+                    key: meta.path.span(),
+                    value: value_span,
+                    data: value,
+                },
+            ) {
+                let mut err = syn::Error::new(path.span(), "duplicate attribute found");
+                err.combine(syn::Error::new(previous, "previous use here"));
+                errors.push(err);
+            }
+            Ok(())
+        })?;
+    }
 
     errors.reverse();
 
@@ -107,3 +167,48 @@ pub fn parse_attrs(
 
     Ok(out)
 }
+
+/// A string literal, or a bare identifier used as if it was one, e.g. `name = Foo` is `name = "Foo"`
+pub fn parse_value(input: ParseStream) -> syn::Result<LitStr> {
+    let lookahead = input.lookahead1();
+    if lookahead.peek(LitStr) {
+        return input.parse();
+    }
+    if lookahead.peek(syn::Ident::peek_any) {
+        let ident = input.call(syn::Ident::parse_any)?;
+        return Ok(LitStr::new(&ident.unraw().to_string(), ident.span()));
+    }
+    Err(lookahead.error())
+}
+
+/// Whether a field has `#[serde(flatten)]`
+///
+/// serde reports its own errors, so attributes that can't be parsed are ignored here
+pub fn serde_flatten(attrs: &[Attribute]) -> bool {
+    let mut flatten = false;
+    for attr in attrs.iter().filter(|c| c.path().is_ident("serde")) {
+        let Ok(list) = attr.meta.require_list() else {
+            continue;
+        };
+        let _ = list.parse_nested_meta(|meta| {
+            if meta.path.is_ident("flatten") {
+                flatten = true;
+            }
+            skip_value(&meta)
+        });
+    }
+    flatten
+}
+
+// skips over the value of an unknown attribute, so it can be reported with the others.
+// it can be a flag, e.g. `foo`, a `foo = value` or a list, e.g. `foo("a", "b")`
+fn skip_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<proc_macro2::TokenStream>()?;
+    }
+    Ok(())
+}
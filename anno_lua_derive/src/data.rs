@@ -1,14 +1,26 @@
 #[derive(Debug)]
 pub struct Field {
+    pub span: proc_macro2::Span,
     pub name: String,
     pub ty: String,
+    pub optional_key: bool,
+    pub index: bool,
+    /// A tuple struct field keyed by its position, e.g. `[1]`
+    pub positional: bool,
     pub docs: Vec<String>,
+    /// A raw annotation written after the `@field`
+    pub cast: Option<String>,
+    /// The class whose fields are used in place of this field
+    pub inline: Option<syn::Type>,
+    /// The `Anno` types this field references
+    pub references: Vec<syn::Type>,
+    /// The referenced types that were guessed by their rust name, and that name
+    pub guessed: Vec<(syn::Type, String)>,
 }
 
 #[derive(Debug)]
 pub struct Variant {
-    pub span: proc_macro2::Span,
-    pub variant: String,
+    pub ident: syn::Ident,
     pub name: String,
     pub discriminant: Discriminant,
     pub docs: Vec<String>,
@@ -18,4 +30,96 @@ pub struct Variant {
 pub enum Discriminant {
     Named(String),
     Number(isize),
+    String(String),
+}
+
+/// An inherent `LUA_NAME` const, so the name is usable without building the whole `Type`
+pub fn lua_name(input: &syn::DeriveInput, name: &str) -> proc_macro2::TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote::quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The name of this type in lua
+            pub const LUA_NAME: &'static str = #name;
+        }
+    }
+}
+
+/// The `file:line` of the span, if `with_location` was requested
+pub fn location(span: proc_macro2::Span, enabled: bool) -> proc_macro2::TokenStream {
+    if !enabled {
+        return quote::quote!(::core::option::Option::None);
+    }
+    quote::quote_spanned! {span=>
+        ::core::option::Option::Some(::core::concat!(::core::file!(), ":", ::core::line!()))
+    }
+}
+
+/// Fails to compile if a referenced type was guessed by its rust name, but is renamed in lua
+///
+/// A type that uses the generics of the derived type can only be checked when the impl is used, in `lua_type`
+pub fn check_guessed(
+    guessed: &[(syn::Type, String)],
+    generics: &syn::Generics,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let (mut items, mut inline) = (vec![], vec![]);
+    for (ty, name) in guessed {
+        let message = format!(
+            "`{name}` has a different lua name, so it can't be guessed. use a `lua_type` instead"
+        );
+        let check = quote::quote_spanned! {syn::spanned::Spanned::span(ty)=>
+            ::core::assert!(
+                anno_lua::__private::str_eq(<#ty>::LUA_NAME, #name),
+                #message,
+            )
+        };
+        if uses_generics(quote::ToTokens::to_token_stream(ty), generics) {
+            inline.push(quote::quote!(const { #check };));
+        } else {
+            items.push(quote::quote!(const _: () = #check;));
+        }
+    }
+    (
+        quote::quote!( #( #items )* ),
+        quote::quote!( #( #inline )* ),
+    )
+}
+
+// whether any of the type, lifetime or const parameters (or `Self`) are in the tokens
+fn uses_generics(tokens: proc_macro2::TokenStream, generics: &syn::Generics) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Group(group) => uses_generics(group.stream(), generics),
+        proc_macro2::TokenTree::Ident(ident) => {
+            ident == "Self"
+                || generics.params.iter().any(|param| match param {
+                    syn::GenericParam::Type(param) => param.ident == ident,
+                    syn::GenericParam::Lifetime(param) => param.lifetime.ident == ident,
+                    syn::GenericParam::Const(param) => param.ident == ident,
+                })
+        }
+        _ => false,
+    })
+}
+
+/// Visits the referenced types, and the dependencies of the inlined types
+pub fn dependencies(
+    references: &[&syn::Type],
+    inlined: &[&syn::Type],
+) -> Option<proc_macro2::TokenStream> {
+    if references.is_empty() && inlined.is_empty() {
+        return None;
+    }
+    Some(quote::quote! {
+        fn dependencies(visitor: &mut dyn anno_lua::Visitor) {
+            #(
+                visitor.visit(
+                    <#references as anno_lua::Anno>::lua_type(),
+                    <#references as anno_lua::Anno>::dependencies,
+                );
+            )*
+            #(
+                <#inlined as anno_lua::Anno>::dependencies(visitor);
+            )*
+        }
+    })
 }
@@ -1,7 +1,7 @@
 use syn::{Attribute, Expr, ExprLit, Lit};
 
 pub fn collect_docs(attrs: &[Attribute]) -> Vec<String> {
-    let mut out = vec![];
+    let mut docs = vec![];
     for input in attrs {
         let Ok(nv) = input.meta.require_name_value() else {
             continue;
@@ -17,7 +17,69 @@ pub fn collect_docs(attrs: &[Attribute]) -> Vec<String> {
             continue;
         };
 
-        out.push(lit.value().trim().to_string());
+        docs.push(lit.value());
     }
-    out
+
+    if docs.is_empty() {
+        return vec![];
+    }
+    split_lines(&docs.join("\n")).collect()
+}
+
+/// Applies the `doc` and `doc_replace` attributes to the collected docs
+///
+/// `doc_replace` replaces the doc comments, `doc` is appended afterwards
+pub fn override_docs(
+    mut docs: Vec<String>,
+    doc: Option<String>,
+    doc_replace: Option<String>,
+) -> Vec<String> {
+    if let Some(replace) = doc_replace {
+        docs = split_lines(&replace).collect();
+    }
+    if let Some(doc) = doc {
+        docs.extend(split_lines(&doc));
+    }
+    docs
+}
+
+/// Appends the `since` attribute to the docs, e.g. `Since: 1.2`
+pub fn since_docs(mut docs: Vec<String>, since: Option<String>) -> Vec<String> {
+    if let Some(since) = since {
+        docs.push(format!("Since: {}", since.trim()));
+    }
+    docs
+}
+
+/// Prepends a `Label: value` line, e.g. `Default: 0`
+pub fn note_docs(mut docs: Vec<String>, label: &str, value: Option<String>) -> Vec<String> {
+    if let Some(value) = value {
+        docs.insert(0, format!("{label}: {}", value.trim()));
+    }
+    docs
+}
+
+// a single doc attribute can span multiple lines (e.g. `/** */` or a
+// `#[doc = "..."]` with embedded CRLFs), so split it and drop any stray `\r`
+//
+// lines inside of a ``` fence keep their indentation, only the space after the
+// `///` is removed, so code examples still render correctly
+fn split_lines(doc: &str) -> impl Iterator<Item = String> + '_ {
+    let mut in_fence = false;
+    doc.split('\n').map(move |line| {
+        let line = line.replace('\r', "");
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            return trimmed.to_string();
+        }
+        if in_fence {
+            return line
+                .strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string();
+        }
+        trimmed.to_string()
+    })
 }
@@ -1,78 +1,192 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use quote::quote;
 use syn::{
-    spanned::Spanned, DataEnum, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Lit, LitStr, UnOp,
-    Variant,
+    ext::IdentExt as _, spanned::Spanned, DataEnum, DeriveInput, Expr, ExprLit, ExprUnary, Fields,
+    Lit, LitStr, UnOp, Variant,
 };
 
 use crate::{
-    attrs::{parse_attrs, Attr, Kind},
+    attrs::{parse_attrs, parse_value, Attr, Kind},
     data,
-    docs::collect_docs,
+    docs::{collect_docs, override_docs, since_docs},
     error::Error,
+    rename::name_template,
 };
 
+const VARIANT_ATTRS: &[(&str, Kind)] = &[
+    ("name", Kind::Name),
+    ("rename", Kind::Name),
+    ("ignore", Kind::Ignore),
+    ("skip", Kind::Ignore),
+    ("value", Kind::Value),
+    ("doc", Kind::Doc),
+    ("doc_replace", Kind::DocReplace),
+    ("since", Kind::Since),
+];
+
 struct EnumMeta {
     use_self: bool,
+    with_location: bool,
+    case_insensitive_names: bool,
+    with_reverse: bool,
     alias: Option<String>,
+    as_alias: bool,
+    as_literal_alias: bool,
+    value_alias: Option<String>,
+    headers: Vec<String>,
     name: String,
+    name_template: Option<LitStr>,
+    doc: Option<String>,
+    doc_replace: Option<String>,
+    since: Option<String>,
 }
 
 impl EnumMeta {
     fn parse(input: &DeriveInput) -> Result<Self, syn::Error> {
-        let Some(attr) = input.attrs.iter().find(|c| c.path().is_ident("anno")) else {
-            return Ok(Self {
-                use_self: false,
-                alias: None,
-                name: input.ident.to_string(),
-            });
-        };
-
         let mut this = Self {
             use_self: false,
+            with_location: false,
+            case_insensitive_names: false,
+            with_reverse: false,
             alias: None,
+            as_alias: false,
+            as_literal_alias: false,
+            value_alias: None,
+            headers: vec![],
             name: String::new(),
+            name_template: None,
+            doc: None,
+            doc_replace: None,
+            since: None,
         };
 
-        attr.meta.require_list()?.parse_nested_meta(|meta| {
-            if meta.path.is_ident("name") {
-                if !this.name.is_empty() {
-                    return Err(syn::Error::new(meta.path.span(), "duplicate name provided"));
+        // where `self`, `alias` and `with_reverse` were used, they can be in different attributes
+        let (mut self_span, mut alias_span, mut reverse_span) = (None, None, None);
+
+        for attr in input.attrs.iter().filter(|c| c.path().is_ident("anno")) {
+            attr.meta.require_list()?.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") || meta.path.is_ident("rename") {
+                    if !this.name.is_empty() {
+                        return Err(Error::DuplicateName(meta.path.span()).into_syn_error());
+                    }
+                    let lit = parse_value(meta.value()?)?;
+                    if lit.value().trim().is_empty() {
+                        return Err(Error::EmptyName(lit.span()).into_syn_error());
+                    }
+                    this.name = lit.value();
                 }
-                let value = meta.value()?;
-                let name = value.parse::<LitStr>()?.value();
-                if name.trim().is_empty() {
-                    return Err(syn::Error::new(value.span(), "name cannot be empty"));
+
+                if meta.path.is_ident("self") {
+                    if let Some(alias) = alias_span {
+                        return Err(Error::SelfAlias(meta.path.span(), alias).into_syn_error());
+                    }
+                    if let Some(reverse) = reverse_span {
+                        return Err(Error::SelfReverse(meta.path.span(), reverse).into_syn_error());
+                    }
+                    self_span = Some(meta.path.span());
+                    this.use_self = true;
                 }
-                this.name = name;
-            }
 
-            if meta.path.is_ident("self") {
-                this.use_self = true;
-            }
+                if meta.path.is_ident("alias") {
+                    if let Some(self_) = self_span {
+                        return Err(Error::SelfAlias(meta.path.span(), self_).into_syn_error());
+                    }
+                    if let Some(reverse) = reverse_span {
+                        return Err(Error::SelfReverse(meta.path.span(), reverse).into_syn_error());
+                    }
+                    alias_span = Some(meta.path.span());
+                    let lit = parse_value(meta.value()?)?;
+                    if lit.value().trim().is_empty() {
+                        return Err(Error::EmptyValue(lit.span(), "alias").into_syn_error());
+                    }
+                    this.alias = Some(lit.value());
+                }
 
-            if meta.path.is_ident("alias") {
-                if this.use_self {
-                    return Err(syn::Error::new(
-                        meta.path.span(),
-                        "self is exclusive with alias",
-                    ));
+                if meta.path.is_ident("as_alias") {
+                    if this.as_literal_alias {
+                        return Err(Error::LiteralAliasExclusive(meta.path.span()).into_syn_error());
+                    }
+                    if this.value_alias.is_some() {
+                        return Err(Error::AsAliasExclusive(meta.path.span()).into_syn_error());
+                    }
+                    this.as_alias = true;
                 }
 
-                let value = meta.value()?;
-                let name = value.parse::<LitStr>()?.value();
-                if name.trim().is_empty() {
-                    return Err(syn::Error::new(value.span(), "alias cannot be empty"));
+                if meta.path.is_ident("as_literal_alias") {
+                    if this.as_alias || this.value_alias.is_some() {
+                        return Err(Error::LiteralAliasExclusive(meta.path.span()).into_syn_error());
+                    }
+                    this.as_literal_alias = true;
+                }
+
+                if meta.path.is_ident("value_alias") {
+                    if this.as_literal_alias {
+                        return Err(Error::LiteralAliasExclusive(meta.path.span()).into_syn_error());
+                    }
+                    if this.as_alias {
+                        return Err(Error::AsAliasExclusive(meta.path.span()).into_syn_error());
+                    }
+
+                    let lit = parse_value(meta.value()?)?;
+                    if lit.value().trim().is_empty() {
+                        return Err(Error::EmptyValue(lit.span(), "value_alias").into_syn_error());
+                    }
+                    this.value_alias = Some(lit.value());
+                }
+
+                if meta.path.is_ident("name_template") {
+                    this.name_template = Some(parse_value(meta.value()?)?);
+                }
+
+                if meta.path.is_ident("header") {
+                    let lit = parse_value(meta.value()?)?;
+                    if lit.value().trim().is_empty() {
+                        return Err(Error::EmptyValue(lit.span(), "header").into_syn_error());
+                    }
+                    this.headers.push(lit.value());
+                }
+
+                if meta.path.is_ident("with_location") {
+                    this.with_location = true;
+                }
+
+                if meta.path.is_ident("case_insensitive_names") {
+                    this.case_insensitive_names = true;
+                }
+
+                if meta.path.is_ident("with_reverse") {
+                    if let Some(previous) = self_span.or(alias_span) {
+                        return Err(Error::SelfReverse(meta.path.span(), previous).into_syn_error());
+                    }
+                    reverse_span = Some(meta.path.span());
+                    this.with_reverse = true;
+                }
+
+                if meta.path.is_ident("doc") {
+                    this.doc = Some(parse_value(meta.value()?)?.value());
+                }
+
+                if meta.path.is_ident("doc_replace") {
+                    this.doc_replace = Some(parse_value(meta.value()?)?.value());
+                }
+
+                if meta.path.is_ident("since") {
+                    this.since = Some(parse_value(meta.value()?)?.value());
                 }
-                this.alias = Some(name);
-            }
 
-            Ok(())
-        })?;
+                Ok(())
+            })?;
+        }
 
+        // an explicit name takes precedence over the template
         if this.name.trim().is_empty() {
-            this.name = input.ident.to_string()
+            this.name = match &this.name_template {
+                Some(template) => {
+                    name_template(template, &input.ident).map_err(Error::into_syn_error)?
+                }
+                None => input.ident.unraw().to_string(),
+            }
         }
 
         Ok(this)
@@ -80,32 +194,57 @@ impl EnumMeta {
 }
 
 pub fn parse(input: &DeriveInput, data: &DataEnum) -> proc_macro::TokenStream {
-    let docs = collect_docs(&input.attrs);
-    let meta = match EnumMeta::parse(input) {
+    let mut meta = match EnumMeta::parse(input) {
         Ok(meta) => meta,
         Err(err) => return err.into_compile_error().into(),
     };
+    let docs = since_docs(
+        override_docs(
+            collect_docs(&input.attrs),
+            meta.doc.take(),
+            meta.doc_replace.take(),
+        ),
+        meta.since.take(),
+    );
 
     let variants = data.variants.iter().collect::<Vec<_>>();
-    let variants = match collect_variants(
+    let (variants, ignored) = match collect_variants(
         &variants,
         meta.alias.as_deref().unwrap_or(&meta.name),
         meta.use_self || meta.alias.is_some(),
+        meta.case_insensitive_names,
     ) {
         Ok(variants) => variants,
         Err(err) => return err.into_compile_error(),
     };
 
-    let anno_enum = make_variant_mapping(&input.ident, &variants);
-
-    let EnumMeta { name, .. } = meta;
+    let anno_enum = make_variant_mapping(input, &variants, &ignored);
+
+    let EnumMeta {
+        name,
+        with_location,
+        as_alias,
+        as_literal_alias,
+        value_alias,
+        with_reverse,
+        headers,
+        ..
+    } = meta;
+    let value_alias = match (as_alias, as_literal_alias, value_alias) {
+        (true, ..) => quote! { anno_lua::ValueAlias::Replace },
+        (false, true, _) => quote! { anno_lua::ValueAlias::Literals },
+        (false, false, Some(name)) => quote! { anno_lua::ValueAlias::Named(#name) },
+        (false, false, None) => quote! { anno_lua::ValueAlias::None },
+    };
     let iter = variants.iter().map(
         |data::Variant {
+             ident,
              name: lua_name,
              discriminant,
              docs,
              ..
          }| {
+            let location = data::location(ident.span(), with_location);
             let discriminant = match discriminant {
                 data::Discriminant::Named(n) => {
                     quote! {
@@ -117,61 +256,90 @@ pub fn parse(input: &DeriveInput, data: &DataEnum) -> proc_macro::TokenStream {
                         anno_lua::Discriminant::Number(#n)
                     }
                 }
+                data::Discriminant::String(s) => {
+                    quote! {
+                        anno_lua::Discriminant::String(#s)
+                    }
+                }
             };
 
             quote! {
                 anno_lua::Variant {
                     name: #lua_name,
                     discriminant: #discriminant,
-                    docs: &[ #( #docs ),* ]
+                    docs: &[ #( #docs ),* ],
+                    location: #location,
                 }
             }
         },
     );
 
     let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lua_name = data::lua_name(input, &name);
     let ast = quote! {
-        impl anno_lua::Anno for #ident {
+        impl #impl_generics anno_lua::Anno for #ident #ty_generics #where_clause {
             fn lua_type() -> anno_lua::Type {
                 anno_lua::Type::Enum(anno_lua::Enum {
+                    headers: &[ #( #headers ),* ],
                     docs: &[ #( #docs ),* ],
                     name: #name,
                     variants: &[ #( #iter ),* ],
+                    value_alias: #value_alias,
+                    reverse: #with_reverse,
                 })
             }
         }
 
         #anno_enum
+
+        #lua_name
     };
 
     ast.into()
 }
 
 fn make_variant_mapping(
-    ident: &syn::Ident,
+    input: &DeriveInput,
     variants: &[data::Variant],
+    ignored: &[syn::Ident],
 ) -> proc_macro2::TokenStream {
-    let names = variants.iter().map(|var| {
-        let variant = &var.variant;
-        let name = &var.name;
-        let path = syn::Ident::new(variant, var.span);
-        quote! {
-            #ident::#path => #name
-        }
-    });
+    let ident = &input.ident;
+    let names = variants
+        .iter()
+        .map(|var| {
+            let path = &var.ident;
+            let name = &var.name;
+            quote! {
+                #ident::#path => #name
+            }
+        })
+        // an ignored variant has no lua name, see `AnnoEnum::variant_name`
+        .chain(ignored.iter().map(|path| {
+            quote! {
+                #ident::#path { .. } => ""
+            }
+        }));
 
     let variants = variants.iter().map(|var| {
-        let variant = &var.variant;
+        let path = &var.ident;
         let name = &var.name;
-        let path = syn::Ident::new(variant, var.span);
         quote! {
             (#name, #ident::#path)
         }
     });
 
+    // the variants are in a `'static` slice
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(Self: 'static));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     quote! {
-        impl anno_lua::AnnoEnum for #ident {
-            fn variants() -> &'static [(&'static str, #ident)] {
+        impl #impl_generics anno_lua::AnnoEnum for #ident #ty_generics #where_clause {
+            fn variants() -> &'static [(&'static str, Self)] {
                 &[ #( #variants ),* ]
             }
 
@@ -188,16 +356,17 @@ fn collect_variants(
     variants: &[&Variant],
     enum_name: &str,
     use_self: bool,
-) -> Result<Vec<data::Variant>, Error> {
+    case_insensitive: bool,
+) -> Result<(Vec<data::Variant>, Vec<syn::Ident>), Error> {
     let mut out = vec![];
+    let mut ignored = vec![];
     let mut errors: Vec<Error> = vec![];
 
-    let mut seen = HashMap::new();
+    let mut seen = BTreeMap::new();
     let mut n = 0;
 
     for variant in variants {
-        let docs = collect_docs(&variant.attrs);
-        let mut kv = match parse_attrs(&variant.attrs, &[("name", Kind::Name)]) {
+        let mut kv = match parse_attrs(&variant.attrs, VARIANT_ATTRS) {
             Ok(kv) => kv,
             Err(err) => {
                 errors.push(err.into());
@@ -205,23 +374,68 @@ fn collect_variants(
             }
         };
 
-        if let Some(span) = kv
-            .iter()
-            .find_map(|(k, Attr { key, .. })| (!matches!(k, Kind::Name)).then_some(key))
-        {
-            errors.push(Error::OnlyName(*span));
+        // ignored variants still count towards the implicit numbering
+        if kv.remove(&Kind::Ignore).is_some() {
+            if !use_self {
+                n += 1;
+            }
+            ignored.push(variant.ident.clone());
             continue;
         }
 
+        let docs = since_docs(
+            override_docs(
+                collect_docs(&variant.attrs),
+                kv.remove(&Kind::Doc).map(|Attr { data, .. }| data),
+                kv.remove(&Kind::DocReplace).map(|Attr { data, .. }| data),
+            ),
+            kv.remove(&Kind::Since).map(|Attr { data, .. }| data),
+        );
+
         let Attr {
             value, data: name, ..
         } = kv.remove(&Kind::Name).unwrap_or_else(|| Attr {
             key: variant.ident.span(),
             value: variant.ident.span(),
-            data: variant.ident.to_string(),
+            data: variant.ident.unraw().to_string(),
         });
 
+        if name.contains('.') {
+            errors.push(Error::DottedName(value));
+            continue;
+        }
+
+        let string_value = match kv.remove(&Kind::Value) {
+            Some(Attr { key, .. }) if use_self => {
+                errors.push(Error::SelfValue(key));
+                continue;
+            }
+            value => value.map(|Attr { data, .. }| data),
+        };
+
         let new = match &variant.fields {
+            Fields::Unit if string_value.is_some() && variant.discriminant.is_some() => {
+                let span = variant
+                    .discriminant
+                    .as_ref()
+                    .map_or_else(|| variant.span(), |(_, expr)| expr.span());
+                errors.push(Error::ValueDiscriminant(span));
+                continue;
+            }
+
+            Fields::Unit if string_value.is_some() => {
+                if !use_self {
+                    n += 1;
+                }
+
+                data::Variant {
+                    ident: variant.ident.clone(),
+                    name,
+                    discriminant: data::Discriminant::String(string_value.unwrap_or_default()),
+                    docs,
+                }
+            }
+
             Fields::Unit if variant.discriminant.is_some() && use_self => {
                 errors.push(Error::SelfDiscriminant(variant.span()));
                 continue;
@@ -241,8 +455,7 @@ fn collect_variants(
                 n += 1;
 
                 data::Variant {
-                    span: variant.span(),
-                    variant: variant.ident.to_string(),
+                    ident: variant.ident.clone(),
                     name,
                     discriminant,
                     docs,
@@ -250,8 +463,7 @@ fn collect_variants(
             }
 
             Fields::Unit => data::Variant {
-                span: variant.span(),
-                variant: variant.ident.to_string(),
+                ident: variant.ident.clone(),
                 name,
                 discriminant: data::Discriminant::Named(enum_name.to_string()),
                 docs,
@@ -263,10 +475,17 @@ fn collect_variants(
             }
         };
 
-        if let Some(prev) = seen.insert(new.name.clone(), value) {
-            let mut err = syn::Error::new(value, "duplicate name found");
-            err.combine(syn::Error::new(prev, "previous used here"));
-            errors.push(err.into());
+        let key = match case_insensitive {
+            true => new.name.to_lowercase(),
+            false => new.name.clone(),
+        };
+        if let Some(previous) = seen.insert(key, value) {
+            errors.push(Error::DuplicateKey {
+                span: value,
+                previous,
+                note: String::new(),
+                previous_note: String::new(),
+            });
             continue;
         }
 
@@ -287,7 +506,7 @@ fn collect_variants(
         return Err(combined.into());
     }
 
-    Ok(out)
+    Ok((out, ignored))
 }
 
 fn eval_expr(expr: &Expr, errors: &mut Vec<Error>) -> Option<isize> {
@@ -1,14 +1,37 @@
 pub enum Error {
     Syn(syn::Error),
     Union(proc_macro2::Span),
-    UnnamedField(proc_macro2::Span),
     TyRequire(proc_macro2::Span),
-    OnlyName(proc_macro2::Span),
     SelfDiscriminant(proc_macro2::Span),
+    SelfAlias(proc_macro2::Span, proc_macro2::Span),
+    SelfReverse(proc_macro2::Span, proc_macro2::Span),
+    SelfValue(proc_macro2::Span),
+    ExactIndex(proc_macro2::Span, proc_macro2::Span),
     ExpectedNumber(proc_macro2::Span),
     OnlyUnitVariants(proc_macro2::Span),
     DuplicateName(proc_macro2::Span),
+    DuplicateKey {
+        span: proc_macro2::Span,
+        previous: proc_macro2::Span,
+        note: String,
+        previous_note: String,
+    },
     EmptyName(proc_macro2::Span),
+    EmptyValue(proc_macro2::Span, &'static str),
+    Transparent(proc_macro2::Span),
+    ValueDiscriminant(proc_macro2::Span),
+    DottedName(proc_macro2::Span),
+    AliasRequired(proc_macro2::Span),
+    AliasGenerics(proc_macro2::Span),
+    AsAliasExclusive(proc_macro2::Span),
+    LiteralAliasExclusive(proc_macro2::Span),
+    UnknownRenameRule(proc_macro2::Span),
+    NameTemplate(proc_macro2::Span),
+    Flatten(proc_macro2::Span),
+    Range(proc_macro2::Span),
+    EmptyClass(proc_macro2::Span),
+    UnknownOperator(proc_macro2::Span),
+    OperatorArgs(proc_macro2::Span, bool),
 }
 
 impl From<syn::Error> for Error {
@@ -21,18 +44,102 @@ impl Error {
     pub fn into_syn_error(self) -> syn::Error {
         let (span, msg) = match self {
             Self::Syn(syn) => return syn,
+            Self::SelfAlias(span, previous) => {
+                let mut err = syn::Error::new(span, "`self` and `alias` are exclusive");
+                err.combine(syn::Error::new(previous, "previous use here"));
+                return err;
+            }
+            Self::SelfReverse(span, previous) => {
+                let mut err = syn::Error::new(
+                    span,
+                    "`with_reverse` cannot be combined with `self` or `alias`, the reverse table needs numeric values",
+                );
+                err.combine(syn::Error::new(previous, "previous use here"));
+                return err;
+            }
+            Self::ExactIndex(span, exact) => {
+                let mut err = syn::Error::new(
+                    span,
+                    "an index signature cannot be used in an `exact` class, it allows any other key",
+                );
+                err.combine(syn::Error::new(exact, "`exact` used here"));
+                return err;
+            }
+            Self::DuplicateKey {
+                span,
+                previous,
+                note,
+                previous_note,
+            } => {
+                let mut err = syn::Error::new(span, format!("duplicate name found{note}"));
+                err.combine(syn::Error::new(
+                    previous,
+                    format!("previous used here{previous_note}"),
+                ));
+                return err;
+            }
+            Self::EmptyValue(span, key) => {
+                return syn::Error::new(span, format!("`{key}` cannot be empty"));
+            }
+            Self::UnknownOperator(span) => {
+                let supported = anno_lua_impl::Operator::SUPPORTED.join(", ");
+                let msg = format!("unknown operator, expected one of {supported}");
+                return syn::Error::new(span, msg);
+            }
+            Self::OperatorArgs(span, true) => (
+                span,
+                "unary operators only take a result, e.g. `operator(\"unm\", \"Vec2\")`",
+            ),
+            Self::OperatorArgs(span, false) => (
+                span,
+                "expected an operator, a right hand side and a result, e.g. `operator(\"add\", \"Vec2\", \"Vec2\")`",
+            ),
             Self::Union(span) => (span, "unions are not supported"),
-            Self::UnnamedField(span) => (span, "unnamed fields are not allowed"),
             Self::TyRequire(span) => (span, "lua_type = \"type\" is required"),
-            Self::OnlyName(span) => (span, "only name = \"name\" is allowed here"),
             Self::SelfDiscriminant(span) => (
                 span,
-                "a discriminant was provided when `self` was requested",
+                "a discriminant was provided when `self` or `alias` was requested",
             ),
             Self::ExpectedNumber(span) => (span, "expected a number here"),
             Self::OnlyUnitVariants(span) => (span, "only unit variants are allowed"),
             Self::DuplicateName(span) => (span, "duplicate name provided"),
             Self::EmptyName(span) => (span, "name cannot be empty"),
+            Self::Transparent(span) => (span, "transparent requires exactly one field"),
+            Self::DottedName(span) => (span, "name cannot contain a `.`"),
+            Self::UnknownRenameRule(span) => (
+                span,
+                "unknown rename rule, expected one of `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case` or `SCREAMING_SNAKE_CASE`",
+            ),
+            Self::NameTemplate(span) => (
+                span,
+                "name_template requires a `{}` for the rust name, e.g. `Lua{}`",
+            ),
+            Self::Flatten(span) => (span, "flatten requires a `HashMap`, `BTreeMap`, `Vec` or array field"),
+            Self::EmptyClass(span) => (
+                span,
+                "this class has no fields, use `#[anno(allow_empty)]` if this is intended",
+            ),
+            Self::Range(span) => (span, "expected an integer range, e.g. `0..=255`"),
+            Self::AliasRequired(span) => (
+                span,
+                "`alias` is required on type aliases, e.g. `#[anno(alias)]`",
+            ),
+            Self::AsAliasExclusive(span) => (span, "as_alias is exclusive with value_alias"),
+            Self::LiteralAliasExclusive(span) => (
+                span,
+                "as_literal_alias is exclusive with as_alias and value_alias",
+            ),
+            Self::AliasGenerics(span) => (
+                span,
+                "generic type aliases are not supported, the marker type cannot name the type parameters",
+            ),
+            Self::ValueDiscriminant(span) => {
+                (span, "a discriminant cannot be combined with `value`")
+            }
+            Self::SelfValue(span) => (
+                span,
+                "`value` cannot be used when `self` or `alias` was requested",
+            ),
         };
         syn::Error::new(span, msg)
     }
@@ -0,0 +1,342 @@
+use syn::ext::IdentExt as _;
+
+/// The type being derived, so references to itself can use its lua name
+#[derive(Copy, Clone)]
+pub struct SelfType<'a> {
+    pub ident: &'a syn::Ident,
+    pub name: &'a str,
+}
+
+impl SelfType<'_> {
+    // `Self` or the bare name of the type, e.g. `Node` or `Node<T>`
+    fn is_self(&self, path: &syn::Path) -> bool {
+        path.leading_colon.is_none()
+            && path.segments.len() == 1
+            && (path.is_ident("Self") || path.segments[0].ident == *self.ident)
+    }
+}
+
+/// How types are guessed
+#[derive(Copy, Clone, Default)]
+pub struct GuessContext<'a> {
+    pub this: Option<SelfType<'a>>,
+    /// `Vec<u8>`, `[u8]` and `[u8; N]` are a `string` rather than an `integer[]`
+    pub bytes_as_string: bool,
+    /// Other types are guessed by their rust name, otherwise they are `any`
+    pub references: bool,
+    /// The generics of the type being derived, its type parameters are used by name
+    pub generics: Option<&'a syn::Generics>,
+}
+
+impl GuessContext<'_> {
+    // e.g. `T` in `Handle<T>`
+    fn is_type_param(&self, path: &syn::Path) -> bool {
+        let (Some(generics), Some(ident)) = (self.generics, path.get_ident()) else {
+            return false;
+        };
+        generics.type_params().any(|param| param.ident == *ident)
+    }
+}
+
+pub fn guess_type(ty: &syn::Type, cx: GuessContext) -> String {
+    classify(ty, cx).unwrap_or_else(|| "any".to_string())
+}
+
+pub fn classify(ty: &syn::Type, cx: GuessContext) -> Option<String> {
+    if cx.bytes_as_string && is_bytes(ty) {
+        return Some("string".to_string());
+    }
+
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => classify_path(&path.path, cx),
+        syn::Type::Reference(reference) => classify(&reference.elem, cx),
+        syn::Type::Paren(paren) => classify(&paren.elem, cx),
+        syn::Type::Group(group) => classify(&group.elem, cx),
+        syn::Type::Slice(slice) => Some(format!("{}[]", classify(&slice.elem, cx)?)),
+        syn::Type::Array(array) => Some(format!("{}[]", classify(&array.elem, cx)?)),
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => Some("nil".to_string()),
+        syn::Type::BareFn(..) => Some("function".to_string()),
+        // closures are functions, any other trait could be anything
+        syn::Type::TraitObject(syn::TypeTraitObject { bounds, .. })
+        | syn::Type::ImplTrait(syn::TypeImplTrait { bounds, .. }) => {
+            let ty = if bounds.iter().any(is_fn_trait) {
+                "function"
+            } else {
+                "any"
+            };
+            Some(ty.to_string())
+        }
+        syn::Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(|elem| classify(elem, cx))
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("[{}]", elems.join(", ")))
+        }
+        _ => None,
+    }
+}
+
+// `Vec<u8>`, `[u8]` or `[u8; N]`
+fn is_bytes(ty: &syn::Type) -> bool {
+    let is_u8 = |ty: &syn::Type| matches!(ty, syn::Type::Path(path) if path.path.is_ident("u8"));
+    match ty {
+        syn::Type::Slice(slice) => is_u8(&slice.elem),
+        syn::Type::Array(array) => is_u8(&array.elem),
+        syn::Type::Path(path) => {
+            let Some(head) = path.path.segments.last() else {
+                return false;
+            };
+            let syn::PathArguments::AngleBracketed(args) = &head.arguments else {
+                return false;
+            };
+            head.ident == "Vec"
+                && matches!(
+                    args.args.first(),
+                    Some(syn::GenericArgument::Type(ty)) if args.args.len() == 1 && is_u8(ty)
+                )
+        }
+        _ => false,
+    }
+}
+
+// `Fn(..) -> R`, `FnMut(..)` or `FnOnce(..)`
+fn is_fn_trait(bound: &syn::TypeParamBound) -> bool {
+    let syn::TypeParamBound::Trait(bound) = bound else {
+        return false;
+    };
+    bound
+        .path
+        .segments
+        .last()
+        .is_some_and(|head| head.ident == "Fn" || head.ident == "FnMut" || head.ident == "FnOnce")
+}
+
+/// The key and value types of a map or list, for an index signature, e.g. `[string] integer`
+///
+/// Lists are indexed by `integer`, types that cannot be classified are `any`
+pub fn index_signature(ty: &syn::Type, cx: GuessContext) -> Option<(String, String)> {
+    let path = match ty {
+        syn::Type::Path(path) => path,
+        syn::Type::Slice(slice) => {
+            return Some(("integer".to_string(), guess_type(&slice.elem, cx)))
+        }
+        syn::Type::Array(array) => {
+            return Some(("integer".to_string(), guess_type(&array.elem, cx)))
+        }
+        _ => return None,
+    };
+
+    let head = path.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &head.arguments else {
+        return None;
+    };
+    let mut args = args.args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(guess_type(ty, cx)),
+        _ => None,
+    });
+
+    match Container::from_ident(&head.ident)? {
+        Container::Map => Some((args.next()?, args.next()?)),
+        Container::Vec | Container::Set => Some(("integer".to_string(), args.next()?)),
+        Container::Option | Container::Pointer | Container::NonZero => None,
+    }
+}
+
+/// The types inside of the containers, e.g. `Foo` and `Bar` for `HashMap<Foo, Vec<Bar>>`
+pub fn referenced_types(ty: &syn::Type, out: &mut Vec<syn::Type>) {
+    match ty {
+        syn::Type::Reference(reference) => referenced_types(&reference.elem, out),
+        syn::Type::Paren(paren) => referenced_types(&paren.elem, out),
+        syn::Type::Group(group) => referenced_types(&group.elem, out),
+        syn::Type::Slice(slice) => referenced_types(&slice.elem, out),
+        syn::Type::Array(array) => referenced_types(&array.elem, out),
+        syn::Type::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .for_each(|elem| referenced_types(elem, out)),
+        syn::Type::Path(path) => {
+            let args = path.path.segments.last().and_then(|head| {
+                Container::from_ident(&head.ident)?;
+                match &head.arguments {
+                    syn::PathArguments::AngleBracketed(args) => Some(&args.args),
+                    _ => None,
+                }
+            });
+            let Some(args) = args else {
+                out.push(ty.clone());
+                return;
+            };
+            for arg in args {
+                if let syn::GenericArgument::Type(ty) = arg {
+                    referenced_types(ty, out)
+                }
+            }
+        }
+        _ => out.push(ty.clone()),
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Container {
+    Option,
+    Vec,
+    // the pointer types are the same as what they point to
+    Pointer,
+    NonZero,
+    Map,
+    Set,
+}
+
+impl Container {
+    fn from_ident(ident: &syn::Ident) -> Option<Self> {
+        let container = match () {
+            _ if ident == "Option" => Self::Option,
+            _ if ident == "Vec" || ident == "VecDeque" => Self::Vec,
+            _ if ["Box", "Rc", "Arc", "Cow", "Cell", "RefCell"]
+                .iter()
+                .any(|pointer| ident == pointer) =>
+            {
+                Self::Pointer
+            }
+            _ if ident == "NonZero" => Self::NonZero,
+            _ if ident == "HashMap" || ident == "BTreeMap" => Self::Map,
+            _ if ident == "HashSet" || ident == "BTreeSet" => Self::Set,
+            _ => return None,
+        };
+        Some(container)
+    }
+}
+
+fn classify_path(path: &syn::Path, cx: GuessContext) -> Option<String> {
+    // only the last segment is looked at, so `std::string::String` and
+    // `std::collections::HashMap<K, V>` are treated the same as their bare forms
+    let head = path.segments.last()?;
+
+    if let Some(this) = cx.this.filter(|this| this.is_self(path)) {
+        return Some(this.name.to_string());
+    }
+
+    if cx.is_type_param(path) {
+        return Some(head.ident.unraw().to_string());
+    }
+
+    let args = match &head.arguments {
+        syn::PathArguments::None => {
+            return classify_primitive(&head.ident)
+                .map(str::to_string)
+                .or_else(|| classify_reference(&head.ident, cx))
+        }
+        syn::PathArguments::AngleBracketed(args) => &args.args,
+        syn::PathArguments::Parenthesized(..) => return None,
+    };
+
+    let mut args = args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+
+    let Some(container) = Container::from_ident(&head.ident) else {
+        // a generic `Anno` type with `reference`, e.g. `Container<String>` is `Container<string>`.
+        // otherwise it is some other type, e.g. `Mutex<T>`, and is `any`
+        let name = classify_reference(&head.ident, cx)?;
+        let args = args
+            .map(|ty| classify(ty, cx))
+            .collect::<Option<Vec<_>>>()?;
+        if args.is_empty() {
+            return Some(name);
+        }
+        return Some(format!("{name}<{}>", args.join(", ")));
+    };
+
+    if container == Container::Map {
+        let (Some(key), Some(value)) = (args.next(), args.next()) else {
+            return None;
+        };
+        let key = classify(key, cx)?;
+        let value = classify(value, cx)?;
+        return Some(format!("table<{key}, {value}>"));
+    }
+
+    let (Some(ty), None) = (args.next(), args.next()) else {
+        return None;
+    };
+
+    let inner = classify(ty, cx)?;
+    let ty = match container {
+        Container::Option => format!("{inner}?"),
+        Container::Vec | Container::Set => format!("{inner}[]"),
+        Container::Pointer | Container::NonZero | Container::Map => inner,
+    };
+    Some(ty)
+}
+
+// with `reference` any other type is another `Anno` type, e.g. a `Direction` enum.
+// only its rust name is known here, so it is checked against its `LUA_NAME`, see `guessed_references`
+fn classify_reference(ident: &syn::Ident, cx: GuessContext) -> Option<String> {
+    let name = ident.unraw().to_string();
+    (cx.references && name != "Self" && name.starts_with(char::is_uppercase)).then_some(name)
+}
+
+/// The referenced types that were guessed by their rust name, and that name
+///
+/// e.g. `Direction` for `Vec<Direction>`, a renamed `Direction` would be wrong
+pub fn guessed_references(ty: &syn::Type, cx: GuessContext) -> Vec<(syn::Type, String)> {
+    let mut references = vec![];
+    referenced_types(ty, &mut references);
+    references
+        .into_iter()
+        .filter_map(|ty| {
+            let syn::Type::Path(path) = &ty else {
+                return None;
+            };
+            if path.qself.is_some()
+                || cx.is_type_param(&path.path)
+                || cx.this.is_some_and(|this| this.is_self(&path.path))
+            {
+                return None;
+            }
+            let head = path.path.segments.last()?;
+            if classify_primitive(&head.ident).is_some() {
+                return None;
+            }
+            let name = classify_reference(&head.ident, cx)?;
+            Some((ty, name))
+        })
+        .collect()
+}
+
+const INTEGERS: &[&str] = &[
+    "i8", "i16", "i32", "i64", "isize", //
+    "u8", "u16", "u32", "u64", "usize",
+];
+
+fn classify_primitive(ident: &syn::Ident) -> Option<&'static str> {
+    if ident == "String" || ident == "str" || ident == "char" {
+        return Some("string");
+    }
+
+    if ident == "f32" || ident == "f64" {
+        return Some("number");
+    }
+
+    if ident == "bool" {
+        return Some("boolean");
+    }
+
+    if INTEGERS.iter().any(|c| ident == c) {
+        return Some("integer");
+    }
+
+    // e.g. `NonZeroU32`, the generic `NonZero<T>` is handled as a container
+    if ident
+        .to_string()
+        .strip_prefix("NonZero")
+        .is_some_and(|int| INTEGERS.contains(&&*int.to_lowercase()))
+    {
+        return Some("integer");
+    }
+
+    None
+}
@@ -1,7 +1,7 @@
-use syn::{parse_macro_input, spanned::Spanned, DeriveInput};
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput, ItemType};
 
 #[proc_macro_derive(Anno, attributes(anno))]
-pub fn anno(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+pub fn derive_anno(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     match &input.data {
         syn::Data::Struct(data) => structs::parse(&input, data),
@@ -10,12 +10,24 @@ pub fn anno(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     }
 }
 
+#[proc_macro_attribute]
+pub fn anno(
+    attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item = parse_macro_input!(input as ItemType);
+    aliases::parse(attr, &item)
+}
+
 mod error;
 
 mod data;
 
 mod attrs;
 mod docs;
+mod guess;
+mod rename;
 
+mod aliases;
 mod enums;
 mod structs;
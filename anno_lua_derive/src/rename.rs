@@ -0,0 +1,91 @@
+use syn::{ext::IdentExt as _, LitStr};
+
+use crate::error::Error;
+
+/// The case styles supported by `rename_all`
+#[derive(Copy, Clone)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    pub fn parse(lit: &LitStr) -> Result<Self, Error> {
+        Ok(match &*lit.value() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            _ => return Err(Error::UnknownRenameRule(lit.span())),
+        })
+    }
+
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        match self {
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            Self::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Self::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| match i {
+                    0 => word.to_lowercase(),
+                    _ => capitalize(word),
+                })
+                .collect(),
+            Self::Snake => join(&words, str::to_lowercase),
+            Self::ScreamingSnake => join(&words, str::to_uppercase),
+        }
+    }
+}
+
+/// The name of a type from a `name_template`, each `{}` is replaced by the rust name, e.g. `Lua{}` is `LuaFoo`
+pub fn name_template(lit: &LitStr, ident: &syn::Ident) -> Result<String, Error> {
+    let template = lit.value();
+    if !template.contains("{}") {
+        return Err(Error::NameTemplate(lit.span()));
+    }
+    Ok(template.replace("{}", &ident.unraw().to_string()))
+}
+
+// words are separated by `_` or start at an uppercase letter, e.g. `foo_bar` and `FooBar`
+fn split_words(name: &str) -> Vec<&str> {
+    let mut out = vec![];
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let mut start = 0;
+        let mut prev_lower = false;
+        for (i, ch) in part.char_indices() {
+            if ch.is_uppercase() && prev_lower {
+                out.push(&part[start..i]);
+                start = i;
+            }
+            prev_lower = ch.is_lowercase() || ch.is_numeric();
+        }
+        out.push(&part[start..]);
+    }
+    out
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |head| {
+        head.to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect()
+    })
+}
+
+fn join(words: &[&str], case: impl Fn(&str) -> String) -> String {
+    words
+        .iter()
+        .map(|word| case(word))
+        .collect::<Vec<_>>()
+        .join("_")
+}
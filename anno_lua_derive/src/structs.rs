@@ -1,63 +1,214 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::BTreeMap;
 
 use quote::quote;
-use syn::{spanned::Spanned, DataStruct, DeriveInput, Fields, LitStr};
+use syn::{
+    ext::IdentExt as _, punctuated::Punctuated, spanned::Spanned, DataStruct, DeriveInput, Fields,
+    LitStr, Token,
+};
 
 use crate::{
-    attrs::{parse_attrs, Attr, Kind},
+    attrs::{parse_attrs, parse_value, serde_flatten, Attr, Kind},
     data,
-    docs::collect_docs,
+    docs::{collect_docs, note_docs, override_docs, since_docs},
     error::Error,
+    guess::{
+        guess_type, guessed_references, index_signature, referenced_types, GuessContext, SelfType,
+    },
+    rename::{name_template, RenameRule},
 };
 
+const FIELD_ATTRS: &[(&str, Kind)] = &[
+    ("lua_type", Kind::Type),
+    ("lua_type_parts", Kind::TypeParts),
+    ("name", Kind::Name),
+    ("rename", Kind::Name),
+    ("ignore", Kind::Ignore),
+    ("skip", Kind::Ignore),
+    ("include", Kind::Include),
+    ("guess", Kind::Guess),
+    ("no_guess", Kind::NoGuess),
+    ("optional_key", Kind::OptionalKey),
+    ("flatten", Kind::Flatten),
+    ("inline", Kind::Inline),
+    ("reference", Kind::Reference),
+    ("doc", Kind::Doc),
+    ("doc_replace", Kind::DocReplace),
+    ("since", Kind::Since),
+    ("cast", Kind::Cast),
+    ("default", Kind::Default),
+    ("range", Kind::Range),
+];
+
 struct ClassMeta {
-    exact: bool,
+    exact: Option<proc_macro2::Span>,
     guess: bool,
+    transparent: bool,
+    with_location: bool,
+    case_insensitive_names: bool,
+    opt_in: bool,
+    allow_empty: bool,
+    bytes_as_string: bool,
+    use_serde: bool,
+    self_refers_to_class: bool,
     name: String,
+    name_template: Option<LitStr>,
+    rename_all: Option<RenameRule>,
+    methods: Vec<(String, String)>,
+    operators: Vec<(String, Option<String>, String)>,
+    headers: Vec<String>,
+    doc: Option<String>,
+    doc_replace: Option<String>,
+    since: Option<String>,
 }
 
 impl ClassMeta {
     fn parse(input: &DeriveInput) -> Result<Self, Error> {
-        let Some(attr) = input.attrs.iter().find(|c| c.path().is_ident("anno")) else {
-            return Ok(Self {
-                exact: false,
-                guess: false,
-                name: input.ident.to_string(),
-            });
-        };
-
         let mut this = Self {
-            exact: false,
+            exact: None,
             guess: false,
+            transparent: false,
+            with_location: false,
+            case_insensitive_names: false,
+            opt_in: false,
+            allow_empty: false,
+            bytes_as_string: false,
+            use_serde: false,
+            self_refers_to_class: false,
             name: String::new(),
+            name_template: None,
+            rename_all: None,
+            methods: vec![],
+            operators: vec![],
+            headers: vec![],
+            doc: None,
+            doc_replace: None,
+            since: None,
         };
 
-        attr.meta.require_list()?.parse_nested_meta(|meta| {
-            if meta.path.is_ident("name") {
-                if !this.name.is_empty() {
-                    return Err(Error::DuplicateName(meta.path.span()).into_syn_error());
+        for attr in input.attrs.iter().filter(|c| c.path().is_ident("anno")) {
+            attr.meta.require_list()?.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") || meta.path.is_ident("rename") {
+                    if !this.name.is_empty() {
+                        return Err(Error::DuplicateName(meta.path.span()).into_syn_error());
+                    }
+                    let lit = parse_value(meta.value()?)?;
+                    if lit.value().trim().is_empty() {
+                        return Err(Error::EmptyName(lit.span()).into_syn_error());
+                    }
+                    this.name = lit.value();
                 }
-                let value = meta.value()?;
-                let name = value.parse::<LitStr>()?.value();
-                if name.trim().is_empty() {
-                    return Err(Error::EmptyName(value.span()).into_syn_error());
+
+                if meta.path.is_ident("rename_all") {
+                    let rule = RenameRule::parse(&parse_value(meta.value()?)?)
+                        .map_err(Error::into_syn_error)?;
+                    this.rename_all = Some(rule);
                 }
-                this.name = name;
-            }
 
-            if meta.path.is_ident("exact") {
-                this.exact = true;
-            }
+                if meta.path.is_ident("method") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let name = content.parse::<LitStr>()?;
+                    if name.value().trim().is_empty() {
+                        return Err(Error::EmptyName(name.span()).into_syn_error());
+                    }
+                    content.parse::<Token![,]>()?;
+                    let ty = content.parse::<LitStr>()?.value();
+                    this.methods.push((name.value(), ty));
+                }
 
-            if meta.path.is_ident("guess") {
-                this.guess = true;
-            }
+                if meta.path.is_ident("operator") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let span = content.span();
+                    let args = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                    let mut args = args.iter().map(LitStr::value);
+                    let name = args.next().unwrap_or_default();
+                    if !anno_lua_impl::Operator::SUPPORTED.contains(&name.trim()) {
+                        return Err(Error::UnknownOperator(span).into_syn_error());
+                    }
+                    let unary = anno_lua_impl::Operator::UNARY.contains(&name.trim());
+                    let operator = match (args.next(), args.next(), args.next()) {
+                        (Some(result), None, None) if unary => (name, None, result),
+                        (Some(rhs), Some(result), None) if !unary => (name, Some(rhs), result),
+                        _ => return Err(Error::OperatorArgs(span, unary).into_syn_error()),
+                    };
+                    this.operators.push(operator);
+                }
+
+                if meta.path.is_ident("name_template") {
+                    this.name_template = Some(parse_value(meta.value()?)?);
+                }
+
+                if meta.path.is_ident("header") {
+                    let lit = parse_value(meta.value()?)?;
+                    if lit.value().trim().is_empty() {
+                        return Err(Error::EmptyValue(lit.span(), "header").into_syn_error());
+                    }
+                    this.headers.push(lit.value());
+                }
+
+                if meta.path.is_ident("exact") {
+                    this.exact = Some(meta.path.span());
+                }
+
+                if meta.path.is_ident("guess") {
+                    this.guess = true;
+                }
+
+                if meta.path.is_ident("transparent") {
+                    this.transparent = true;
+                }
+
+                if meta.path.is_ident("with_location") {
+                    this.with_location = true;
+                }
 
-            Ok(())
-        })?;
+                if meta.path.is_ident("case_insensitive_names") {
+                    this.case_insensitive_names = true;
+                }
+
+                if meta.path.is_ident("opt_in") {
+                    this.opt_in = true;
+                }
+
+                if meta.path.is_ident("allow_empty") {
+                    this.allow_empty = true;
+                }
+
+                if meta.path.is_ident("bytes_as_string") {
+                    this.bytes_as_string = true;
+                }
+
+                if meta.path.is_ident("use_serde") {
+                    this.use_serde = true;
+                }
+
+                if meta.path.is_ident("self_refers_to_class") {
+                    this.self_refers_to_class = true;
+                }
 
+                if meta.path.is_ident("doc") {
+                    this.doc = Some(parse_value(meta.value()?)?.value());
+                }
+
+                if meta.path.is_ident("doc_replace") {
+                    this.doc_replace = Some(parse_value(meta.value()?)?.value());
+                }
+
+                if meta.path.is_ident("since") {
+                    this.since = Some(parse_value(meta.value()?)?.value());
+                }
+
+                Ok(())
+            })?;
+        }
+
+        // an explicit name takes precedence over the template
         if this.name.trim().is_empty() {
-            this.name = input.ident.to_string()
+            this.name = match &this.name_template {
+                Some(template) => name_template(template, &input.ident)?,
+                None => input.ident.unraw().to_string(),
+            }
         }
 
         Ok(this)
@@ -65,61 +216,359 @@ impl ClassMeta {
 }
 
 pub fn parse(input: &DeriveInput, data: &DataStruct) -> proc_macro::TokenStream {
-    let docs = collect_docs(&input.attrs);
-    let meta = match ClassMeta::parse(input) {
+    let mut meta = match ClassMeta::parse(input) {
         Ok(meta) => meta,
         Err(err) => return err.into_compile_error(),
     };
+    let docs = since_docs(
+        override_docs(
+            collect_docs(&input.attrs),
+            meta.doc.take(),
+            meta.doc_replace.take(),
+        ),
+        meta.since.take(),
+    );
+
+    let cx = GuessContext {
+        this: Some(SelfType {
+            ident: &input.ident,
+            name: &meta.name,
+        }),
+        bytes_as_string: meta.bytes_as_string,
+        references: false,
+        generics: Some(&input.generics),
+    };
+
+    if meta.transparent {
+        return parse_transparent(input, data, cx, &meta.name, &docs);
+    }
+
+    // a tuple struct is an alias, of its single field or of a tuple of its fields.
+    // if any of its fields has a `name` it is a class instead, as is one with only `PhantomData`
+    if let Fields::Unnamed(fields) = &data.fields {
+        let named = fields.unnamed.iter().any(|field| {
+            parse_attrs(&field.attrs, FIELD_ATTRS).is_ok_and(|kvs| kvs.contains_key(&Kind::Name))
+        });
+        if !named {
+            let fields = fields
+                .unnamed
+                .iter()
+                .filter(|field| !is_phantom_data(&field.ty));
+            match fields.count() {
+                0 => {}
+                1 => return parse_transparent(input, data, cx, &meta.name, &docs),
+                _ => return parse_tuple(input, data, cx, &meta.name, &docs),
+            }
+        }
+    }
 
-    let fields = match collect_fields(&data.fields, meta.guess) {
+    let fields = match collect_fields(&data.fields, &meta, cx) {
         Ok(fields) => fields,
         Err(err) => return err.into_compile_error(),
     };
 
-    let ClassMeta { exact, name, .. } = meta;
+    // an exact class can't have any other keys, so an index signature contradicts it
+    if let Some(exact) = meta.exact {
+        if let Some(field) = fields.iter().find(|field| field.index) {
+            return Error::ExactIndex(field.span, exact).into_compile_error();
+        }
+    }
+
+    // every field being ignored is usually a mistake
+    if fields.is_empty()
+        && meta.methods.is_empty()
+        && meta.operators.is_empty()
+        && !meta.allow_empty
+    {
+        return Error::EmptyClass(input.ident.span()).into_compile_error();
+    }
+
+    let ClassMeta {
+        exact,
+        name,
+        methods,
+        operators,
+        headers,
+        with_location,
+        self_refers_to_class,
+        ..
+    } = meta;
+    let exact = exact.is_some();
+
+    let iter = fields.iter().map(
+        |data::Field {
+             span,
+             name,
+             ty,
+             optional_key,
+             index,
+             positional,
+             docs,
+             cast,
+             inline,
+             ..
+         }| {
+            let location = data::location(*span, with_location);
+            let cast = match cast {
+                Some(cast) => quote!(::core::option::Option::Some(#cast)),
+                None => quote!(::core::option::Option::None),
+            };
+            let inline = match inline {
+                Some(ty) => quote!(::core::option::Option::Some(anno_lua::LuaTypeFn::Fn(<#ty as anno_lua::Anno>::lua_type))),
+                None => quote!(::core::option::Option::None),
+            };
+            quote! {
+                anno_lua::Field {
+                    name: #name,
+                    ty: #ty,
+                    optional_key: #optional_key,
+                    index: #index,
+                    positional: #positional,
+                    docs: &[ #( #docs ),* ],
+                    location: #location,
+                    cast: #cast,
+                    inline: #inline,
+                }
+            }
+        },
+    );
+
+    let references = fields
+        .iter()
+        .flat_map(|field| &field.references)
+        .collect::<Vec<_>>();
+    // an inlined class isn't generated by itself, but what it references is
+    let inlined = fields
+        .iter()
+        .filter_map(|field| field.inline.as_ref())
+        .collect::<Vec<_>>();
+    let dependencies = data::dependencies(&references, &inlined);
 
-    let iter = fields.iter().map(|data::Field { name, ty, docs }| {
+    let guessed = fields
+        .iter()
+        .flat_map(|field| field.guessed.iter().cloned())
+        .collect::<Vec<_>>();
+    let (checks, inline_checks) = data::check_guessed(&guessed, &input.generics);
+
+    let methods = methods.iter().map(|(name, ty)| {
         quote! {
-            anno_lua::Field {
+            anno_lua::Method {
                 name: #name,
                 ty: #ty,
-                docs: &[ #( #docs ),* ]
             }
         }
     });
 
+    let operators = operators.iter().map(|(name, rhs, result)| {
+        let rhs = match rhs {
+            Some(rhs) => quote!(::core::option::Option::Some(#rhs)),
+            None => quote!(::core::option::Option::None),
+        };
+        quote! {
+            anno_lua::Operator {
+                name: #name,
+                rhs: #rhs,
+                result: #result,
+            }
+        }
+    });
+
+    let generics = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.unraw().to_string());
+
     let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lua_name = data::lua_name(input, &name);
     let ast = quote! {
-        impl anno_lua::Anno for #ident {
+        impl #impl_generics anno_lua::Anno for #ident #ty_generics #where_clause {
             fn lua_type() -> anno_lua::Type {
+                #inline_checks
                 anno_lua::Type::Class(anno_lua::Class{
                     exact: #exact,
+                    headers: &[ #( #headers ),* ],
                     docs: &[ #( #docs ),* ],
                     name: #name,
+                    generics: &[ #( #generics ),* ],
                     fields: &[ #( #iter ),* ],
+                    methods: &[ #( #methods ),* ],
+                    operators: &[ #( #operators ),* ],
+                    self_refers_to_class: #self_refers_to_class,
+                })
+            }
+
+            #dependencies
+        }
+
+        #lua_name
+        #checks
+    };
+
+    ast.into()
+}
+
+fn parse_transparent(
+    input: &DeriveInput,
+    data: &DataStruct,
+    cx: GuessContext,
+    name: &str,
+    docs: &[String],
+) -> proc_macro::TokenStream {
+    let mut fields = data
+        .fields
+        .iter()
+        .filter(|field| !is_phantom_data(&field.ty));
+    let (Some(field), None) = (fields.next(), fields.next()) else {
+        return Error::Transparent(input.ident.span()).into_compile_error();
+    };
+
+    let AliasField {
+        ty,
+        references,
+        guessed,
+    } = match alias_field_type(field, cx) {
+        Ok(field) => field,
+        Err(err) => return Error::from(err).into_compile_error(),
+    };
+
+    let (checks, inline_checks) = data::check_guessed(&guessed, &input.generics);
+    let dependencies = data::dependencies(&references.iter().collect::<Vec<_>>(), &[]);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lua_name = data::lua_name(input, name);
+    let ast = quote! {
+        impl #impl_generics anno_lua::Anno for #ident #ty_generics #where_clause {
+            fn lua_type() -> anno_lua::Type {
+                #inline_checks
+                anno_lua::Type::Alias(anno_lua::Alias {
+                    docs: &[ #( #docs ),* ],
+                    name: #name,
+                    ty: #ty,
+                })
+            }
+
+            #dependencies
+        }
+
+        #lua_name
+        #checks
+    };
+
+    ast.into()
+}
+
+fn parse_tuple(
+    input: &DeriveInput,
+    data: &DataStruct,
+    cx: GuessContext,
+    name: &str,
+    docs: &[String],
+) -> proc_macro::TokenStream {
+    let fields = match data
+        .fields
+        .iter()
+        .filter(|field| !is_phantom_data(&field.ty))
+        .map(|field| alias_field_type(field, cx))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(fields) => fields,
+        Err(err) => return Error::from(err).into_compile_error(),
+    };
+    let types = fields.iter().map(|field| &*field.ty).collect::<Vec<_>>();
+    let ty = format!("[{}]", types.join(", "));
+
+    let guessed = fields
+        .iter()
+        .flat_map(|field| field.guessed.iter().cloned())
+        .collect::<Vec<_>>();
+    let (checks, inline_checks) = data::check_guessed(&guessed, &input.generics);
+    let references = fields
+        .iter()
+        .flat_map(|field| &field.references)
+        .collect::<Vec<_>>();
+    let dependencies = data::dependencies(&references, &[]);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lua_name = data::lua_name(input, name);
+    let ast = quote! {
+        impl #impl_generics anno_lua::Anno for #ident #ty_generics #where_clause {
+            fn lua_type() -> anno_lua::Type {
+                #inline_checks
+                anno_lua::Type::Alias(anno_lua::Alias {
+                    docs: &[ #( #docs ),* ],
+                    name: #name,
+                    ty: #ty,
                 })
             }
+
+            #dependencies
         }
+
+        #lua_name
+        #checks
     };
 
     ast.into()
 }
 
-fn collect_fields(fields: &Fields, guess: bool) -> Result<Vec<data::Field>, Error> {
+// a field of a tuple alias
+struct AliasField {
+    ty: String,
+    references: Vec<syn::Type>,
+    guessed: Vec<(syn::Type, String)>,
+}
+
+// the `lua_type` of the field, otherwise its guessed type
+fn alias_field_type(field: &syn::Field, cx: GuessContext) -> Result<AliasField, syn::Error> {
+    let mut kvs = parse_attrs(
+        &field.attrs,
+        &[
+            ("lua_type", Kind::Type),
+            ("lua_type_parts", Kind::TypeParts),
+            ("reference", Kind::Reference),
+        ],
+    )?;
+
+    let reference = kvs.remove(&Kind::Reference).is_some();
+    let cx = GuessContext {
+        references: reference,
+        ..cx
+    };
+
+    let lua_type = kvs.remove(&Kind::Type).map(|Attr { data, .. }| data);
+    let mut references = vec![];
+    let mut guessed = vec![];
+    if reference {
+        referenced_types(&field.ty, &mut references);
+        if lua_type.as_ref().is_none_or(|ty| ty.contains("{ty}")) {
+            guessed = guessed_references(&field.ty, cx);
+        }
+    }
+
+    let guess = guess_type(&field.ty, cx);
+    let ty = lua_type.map_or_else(|| guess.clone(), |ty| interpolate(&ty, &guess));
+    Ok(AliasField {
+        ty,
+        references,
+        guessed,
+    })
+}
+
+fn collect_fields(
+    fields: &Fields,
+    meta: &ClassMeta,
+    cx: GuessContext,
+) -> Result<Vec<data::Field>, Error> {
     let mut out = vec![];
     let mut errors = vec![];
 
-    let mut seen = HashMap::new();
-
-    for field in fields {
-        let mut kvs = match parse_attrs(
-            &field.attrs,
-            &[
-                ("lua_type", Kind::Type),
-                ("name", Kind::Name),
-                ("ignore", Kind::Ignore),
-            ],
-        ) {
+    let mut seen = BTreeMap::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let mut kvs = match parse_attrs(&field.attrs, FIELD_ATTRS) {
             Ok(kvs) => kvs,
             Err(err) => {
                 errors.push(err);
@@ -127,51 +576,176 @@ fn collect_fields(fields: &Fields, guess: bool) -> Result<Vec<data::Field>, Erro
             }
         };
 
-        match &field.ident {
-            Some(name) => {
-                if kvs.remove(&Kind::Ignore).is_some() {
-                    continue;
-                }
+        if kvs.remove(&Kind::Ignore).is_some() || is_phantom_data(&field.ty) {
+            continue;
+        }
+
+        // with `opt_in` only the fields that ask to be included are used
+        let included = kvs.remove(&Kind::Include).is_some() || kvs.contains_key(&Kind::Type);
+        if meta.opt_in && !included {
+            continue;
+        }
 
-                let Attr {
-                    value, data: name, ..
-                } = kvs.remove(&Kind::Name).unwrap_or_else(|| Attr {
-                    key: field.ident.span(),
-                    value: field.ident.span(),
-                    data: name.to_string(),
-                });
-
-                let ty = kvs.remove(&Kind::Type).map(|Attr { data, .. }| data);
-                let ty = if guess {
-                    ty.unwrap_or_else(|| {
-                        if let syn::Type::Path(path) = &field.ty {
-                            try_classify_type(&path.path)
-                        } else {
-                            None
-                        }
-                        .unwrap_or_else(|| "any".to_string())
-                    })
-                } else {
-                    ty.ok_or_else(|| Error::TyRequire(field.ident.span()))?
-                };
-
-                let new = data::Field {
-                    name,
-                    ty,
-                    docs: collect_docs(&field.attrs),
-                };
-
-                if let Some(prev) = seen.insert(new.name.clone(), value) {
-                    let mut err = syn::Error::new(value, "duplicate name found");
-                    err.combine(syn::Error::new(prev, "previous used here"));
-                    errors.push(err);
-                    continue;
+        // a tuple struct field without a `name` is keyed by its position in lua, e.g. `[1]`
+        let span = field
+            .ident
+            .as_ref()
+            .map_or_else(|| field.ty.span(), |ident| ident.span());
+        let positional = field.ident.is_none() && !kvs.contains_key(&Kind::Name);
+
+        // an explicit name always wins over `rename_all`
+        let renamed =
+            meta.rename_all.is_some() && field.ident.is_some() && !kvs.contains_key(&Kind::Name);
+        let Attr {
+            value, data: name, ..
+        } = kvs.remove(&Kind::Name).unwrap_or_else(|| {
+            let data = match &field.ident {
+                Some(ident) => {
+                    let name = ident.unraw().to_string();
+                    meta.rename_all
+                        .map_or(name.clone(), |rule| rule.apply(&name))
                 }
+                None => (i + 1).to_string(),
+            };
+            Attr {
+                key: span,
+                value: span,
+                data,
+            }
+        });
+
+        if name.contains('.') {
+            errors.push(Error::DottedName(value).into_syn_error());
+            continue;
+        }
 
-                out.push(new)
+        let range = match kvs.remove(&Kind::Range) {
+            Some(Attr { value, data, .. }) if !is_range(&data) => {
+                errors.push(Error::Range(value).into_syn_error());
+                continue;
             }
-            None => return Err(Error::UnnamedField(field.span())),
+            range => range.map(|Attr { data, .. }| data),
+        };
+
+        let ty = kvs.remove(&Kind::Type).map(|Attr { data, .. }| data);
+        let no_guess = kvs.remove(&Kind::NoGuess).is_some();
+
+        // other types are only guessed by name for a field that references them
+        let reference = kvs.remove(&Kind::Reference).is_some();
+        let cx = GuessContext {
+            references: reference,
+            ..cx
+        };
+
+        // serde flattens a map into the extra keys, and anything else into its fields
+        let serde_flatten = meta.use_serde && serde_flatten(&field.attrs);
+        let (serde_inline, serde_index) = match index_signature(&field.ty, cx).is_some() {
+            true => (false, serde_flatten),
+            false => (serde_flatten, false),
+        };
+
+        // the fields of an inlined class are resolved when generating,
+        // so this type is only used if it turns out to not be a class
+        let inline =
+            (kvs.remove(&Kind::Inline).is_some() || serde_inline).then(|| field.ty.clone());
+
+        // the guessed names of the referenced types are checked, unless the type is replaced
+        let guessed = match &ty {
+            _ if !reference || inline.is_some() => vec![],
+            Some(ty) if !ty.contains("{ty}") => vec![],
+            None if no_guess => vec![],
+            _ => guessed_references(&field.ty, cx),
+        };
+
+        // the key type is used as the name of an index signature
+        let (name, ty, index) = if inline.is_some() {
+            let ty = ty.map(|ty| interpolate(&ty, &guess_type(&field.ty, cx)));
+            (name, ty.unwrap_or_else(|| guess_type(&field.ty, cx)), false)
+        } else if kvs.remove(&Kind::Flatten).is_some() || serde_index {
+            let Some((key, value)) = index_signature(&field.ty, cx) else {
+                errors.push(Error::Flatten(field.ty.span()).into_syn_error());
+                continue;
+            };
+            let ty = ty.map_or_else(|| value.clone(), |ty| interpolate(&ty, &value));
+            (key, ty, true)
+        } else {
+            let ty = ty.map(|ty| interpolate(&ty, &guess_type(&field.ty, cx)));
+            let ty = if no_guess {
+                ty.unwrap_or_else(|| "any".to_string())
+            } else if meta.guess || kvs.remove(&Kind::Guess).is_some() || field.ident.is_none() {
+                // tuple struct fields are always guessed, like the fields of a tuple alias
+                ty.unwrap_or_else(|| guess_type(&field.ty, cx))
+            } else {
+                ty.ok_or(Error::TyRequire(span))?
+            };
+            (name, ty, false)
+        };
+
+        let docs = override_docs(
+            collect_docs(&field.attrs),
+            kvs.remove(&Kind::Doc).map(|Attr { data, .. }| data),
+            kvs.remove(&Kind::DocReplace).map(|Attr { data, .. }| data),
+        );
+        let docs = note_docs(docs, "Range", range);
+        let docs = note_docs(
+            docs,
+            "Default",
+            kvs.remove(&Kind::Default).map(|Attr { data, .. }| data),
+        );
+
+        let new = data::Field {
+            span,
+            name,
+            ty,
+            optional_key: kvs.remove(&Kind::OptionalKey).is_some(),
+            index,
+            positional: positional && inline.is_none(),
+            cast: kvs.remove(&Kind::Cast).map(|Attr { data, .. }| data),
+            inline,
+            references: {
+                let mut references = vec![];
+                if reference {
+                    referenced_types(&field.ty, &mut references);
+                }
+                references
+            },
+            guessed,
+            docs: since_docs(docs, kvs.remove(&Kind::Since).map(|Attr { data, .. }| data)),
+        };
+
+        // the name of an inlined field is never used
+        if new.inline.is_some() {
+            out.push(new);
+            continue;
+        }
+
+        let key = match new.index || new.positional {
+            true => format!("[{}]", new.name),
+            false => new.name.clone(),
+        };
+        let key = match meta.case_insensitive_names {
+            true => key.to_lowercase(),
+            false => key,
+        };
+        // point out the `rename_all` in case the collision isn't obvious from the source
+        let note = match renamed {
+            true => format!(", renamed to `{}` by `rename_all`", new.name),
+            false => String::new(),
+        };
+        if let Some((previous, previous_note)) = seen.insert(key, (value, note.clone())) {
+            errors.push(
+                Error::DuplicateKey {
+                    span: value,
+                    previous,
+                    note,
+                    previous_note,
+                }
+                .into_syn_error(),
+            );
+            continue;
         }
+
+        out.push(new)
     }
 
     errors.reverse();
@@ -186,81 +760,41 @@ fn collect_fields(fields: &Fields, guess: bool) -> Result<Vec<data::Field>, Erro
     Ok(out)
 }
 
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
-enum Container {
-    #[default]
-    None,
-    Option,
-    Vec,
+// `{ty}` in a `lua_type` is replaced with the guessed type, e.g. `{ty}?`
+fn interpolate(lua_type: &str, guessed: &str) -> String {
+    lua_type.replace("{ty}", guessed)
 }
 
-fn try_classify_type(path: &syn::Path) -> Option<String> {
-    let mut queue = VecDeque::from_iter([(String::new(), path)]);
-
-    while let Some((mut buf, path)) = queue.pop_front() {
-        let ident = match path.get_ident() {
-            Some(ident) => ident,
-            None => {
-                if path.segments.len() > 1 {
-                    return None;
-                }
-                let head = path.segments.first()?;
-
-                let container = match () {
-                    _ if head.ident == "Option" => Container::Option,
-                    _ if head.ident == "Vec" => Container::Vec,
-                    _ => return None,
-                };
-
-                let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
-                    args,
-                    ..
-                }) = &head.arguments
-                else {
-                    return None;
-                };
-
-                if args.len() > 1 {
-                    return None;
-                }
-
-                let syn::GenericArgument::Type(syn::Type::Path(path)) = args.first()? else {
-                    return None;
-                };
-
-                match container {
-                    Container::None => {}
-                    Container::Option => buf.push_str("?"),
-                    Container::Vec => buf.push_str("[]"),
-                }
-
-                queue.push_back((buf, &path.path));
-                continue;
-            }
-        };
-
-        if ident == "String" {
-            return Some(format!("string{buf}"));
-        }
-
-        if ident == "f32" || ident == "f64" {
-            return Some(format!("number{buf}"));
-        }
-
-        if ident == "bool" {
-            return Some(format!("boolean{buf}"));
-        }
+fn is_phantom_data(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "PhantomData")
+}
 
-        if [
-            "i8", "i16", "i32", "i64", "isize", //
-            "u8", "u16", "u32", "u64", "usize",
-        ]
-        .iter()
-        .any(|c| ident == c)
-        {
-            return Some(format!("integer{buf}"));
+// e.g. `0..=255`, `..10` or `-1..`, the bounds have to be integers
+fn is_range(range: &str) -> bool {
+    fn is_integer(expr: &syn::Expr) -> bool {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(..),
+                ..
+            }) => true,
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(..),
+                expr,
+                ..
+            }) => is_integer(expr),
+            _ => false,
         }
     }
 
-    None
+    let Ok(range) = syn::parse_str::<syn::ExprRange>(range) else {
+        return false;
+    };
+    let ok = |bound: &Option<Box<syn::Expr>>| bound.as_deref().is_none_or(is_integer);
+    (range.start.is_some() || range.end.is_some()) && ok(&range.start) && ok(&range.end)
 }
@@ -0,0 +1,77 @@
+use core::fmt::Write;
+
+use crate::{Discriminant, Type};
+
+// an indented outline of the type, e.g.
+//
+// class Foo
+//   docs: "some docs"
+//   field count: integer
+pub(crate) fn debug_tree(out: &mut impl Write, ty: &Type) -> core::fmt::Result {
+    match ty {
+        Type::Class(class) => {
+            write!(out, "class {}", class.name)?;
+            if !class.generics.is_empty() {
+                write!(out, "<{}>", class.generics.join(", "))?;
+            }
+            if class.exact {
+                write!(out, " (exact)")?;
+            }
+            writeln!(out)?;
+            docs(out, "  ", class.docs)?;
+
+            for field in class.fields {
+                match field.index || field.positional {
+                    true => write!(out, "  field [{}]: {}", field.name, field.ty)?,
+                    false => write!(out, "  field {}: {}", field.name, field.ty)?,
+                }
+                if field.optional_key {
+                    write!(out, " (optional key)")?;
+                }
+                if field.inline.is_some() {
+                    write!(out, " (inline)")?;
+                }
+                if let Some(location) = field.location {
+                    write!(out, " @ {location}")?;
+                }
+                writeln!(out)?;
+                docs(out, "    ", field.docs)?;
+            }
+
+            for method in class.methods {
+                writeln!(out, "  method {}: {}", method.name, method.ty)?;
+            }
+        }
+        Type::Enum(enum_) => {
+            writeln!(out, "enum {}", enum_.name)?;
+            docs(out, "  ", enum_.docs)?;
+
+            for variant in enum_.variants {
+                write!(out, "  variant {} = ", variant.name)?;
+                match variant.discriminant {
+                    Discriminant::Number(n) => write!(out, "{n}")?,
+                    Discriminant::Named(name) => write!(out, "{name}")?,
+                    Discriminant::String(s) => write!(out, "{s:?}")?,
+                }
+                if let Some(location) = variant.location {
+                    write!(out, " @ {location}")?;
+                }
+                writeln!(out)?;
+                docs(out, "    ", variant.docs)?;
+            }
+        }
+        Type::Alias(alias) => {
+            writeln!(out, "alias {} = {}", alias.name, alias.ty)?;
+            docs(out, "  ", alias.docs)?;
+        }
+        Type::Builtin(name) => writeln!(out, "builtin {name}")?,
+    }
+    Ok(())
+}
+
+fn docs(out: &mut impl Write, indent: &str, docs: &[&str]) -> core::fmt::Result {
+    for doc in docs {
+        writeln!(out, "{indent}docs: {doc:?}")?;
+    }
+    Ok(())
+}
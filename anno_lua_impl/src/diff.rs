@@ -0,0 +1,311 @@
+use alloc::vec::Vec;
+
+use crate::{Discriminant, Field, Type, Variant};
+
+/// A change between two versions of a [`Type`], see [`diff_types`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypeChange {
+    /// The lua name of the type changed
+    Renamed {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// The type is a different kind of type, e.g. a `class` is now an `enum`
+    KindChanged {
+        from: &'static str,
+        to: &'static str,
+    },
+    FieldAdded {
+        name: &'static str,
+    },
+    FieldRemoved {
+        name: &'static str,
+    },
+    /// A field has a new name, but the same type and position
+    FieldRenamed {
+        from: &'static str,
+        to: &'static str,
+    },
+    FieldTypeChanged {
+        name: &'static str,
+        from: &'static str,
+        to: &'static str,
+    },
+    VariantAdded {
+        name: &'static str,
+    },
+    VariantRemoved {
+        name: &'static str,
+    },
+    /// A variant has a new name, but the same value and position
+    VariantRenamed {
+        from: &'static str,
+        to: &'static str,
+    },
+    VariantValueChanged {
+        name: &'static str,
+        from: Discriminant,
+        to: Discriminant,
+    },
+    /// The aliased type changed, e.g. `integer` is now `string`
+    AliasTypeChanged {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Some other part of the type changed, e.g. `exact`, `generics` or `headers`
+    Changed {
+        what: &'static str,
+    },
+    /// Some other part of a field changed, e.g. `optional_key` or `index`
+    FieldChanged {
+        name: &'static str,
+        what: &'static str,
+    },
+}
+
+impl core::fmt::Display for TypeChange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Renamed { from, to } => write!(f, "renamed from `{from}` to `{to}`"),
+            Self::KindChanged { from, to } => write!(f, "changed from `{from}` to `{to}`"),
+            Self::FieldAdded { name } => write!(f, "field `{name}` was added"),
+            Self::FieldRemoved { name } => write!(f, "field `{name}` was removed"),
+            Self::FieldRenamed { from, to } => write!(f, "field `{from}` was renamed to `{to}`"),
+            Self::FieldTypeChanged { name, from, to } => {
+                write!(f, "field `{name}` changed from `{from}` to `{to}`")
+            }
+            Self::VariantAdded { name } => write!(f, "variant `{name}` was added"),
+            Self::VariantRemoved { name } => write!(f, "variant `{name}` was removed"),
+            Self::VariantRenamed { from, to } => {
+                write!(f, "variant `{from}` was renamed to `{to}`")
+            }
+            Self::VariantValueChanged { name, from, to } => {
+                write!(f, "variant `{name}` changed from ")?;
+                discriminant(f, from)?;
+                f.write_str(" to ")?;
+                discriminant(f, to)
+            }
+            Self::AliasTypeChanged { from, to } => write!(f, "changed from `{from}` to `{to}`"),
+            Self::Changed { what } => write!(f, "`{what}` changed"),
+            Self::FieldChanged { name, what } => write!(f, "field `{name}` changed its `{what}`"),
+        }
+    }
+}
+
+fn discriminant(
+    f: &mut core::fmt::Formatter<'_>,
+    discriminant: &Discriminant,
+) -> core::fmt::Result {
+    match discriminant {
+        Discriminant::Number(n) => write!(f, "`{n}`"),
+        Discriminant::Named(name) => write!(f, "`{name}`"),
+        Discriminant::String(s) => write!(f, "`\"{s}\"`"),
+    }
+}
+
+/// The changes to the lua facing parts of a type, e.g. to fail CI when the generated stubs change unexpectedly
+///
+/// Fields and variants are matched by name. One that was removed and one that was added at the same position,
+/// with the same type (or value), is a rename
+///
+/// Docs and locations are not compared, any other difference is a change. This is empty if the types are the same
+pub fn diff_types(old: &Type, new: &Type) -> Vec<TypeChange> {
+    let mut changes = Vec::new();
+    if old == new {
+        return changes;
+    }
+
+    if old.name().trim() != new.name().trim() {
+        changes.push(TypeChange::Renamed {
+            from: old.name().trim(),
+            to: new.name().trim(),
+        });
+    }
+
+    match (old, new) {
+        (Type::Class(old), Type::Class(new)) => {
+            changed(&mut changes, "exact", old.exact != new.exact);
+            changed(
+                &mut changes,
+                "headers",
+                !same_strs(old.headers, new.headers),
+            );
+            changed(
+                &mut changes,
+                "generics",
+                !same_strs(old.generics, new.generics),
+            );
+            changed(&mut changes, "methods", old.methods != new.methods);
+            changed(&mut changes, "operators", old.operators != new.operators);
+            changed(
+                &mut changes,
+                "self_refers_to_class",
+                old.self_refers_to_class != new.self_refers_to_class,
+            );
+
+            let same = |old: &Field, new: &Field| field_changes(old, new).next().is_none();
+            for member in diff_members(old.fields, new.fields, |field| field.name, same) {
+                let change = match member {
+                    Member::Changed(old, new) => {
+                        changes.extend(field_changes(old, new));
+                        continue;
+                    }
+                    Member::Renamed(old, new) => TypeChange::FieldRenamed {
+                        from: old.name.trim(),
+                        to: new.name.trim(),
+                    },
+                    Member::Removed(old) => TypeChange::FieldRemoved {
+                        name: old.name.trim(),
+                    },
+                    Member::Added(new) => TypeChange::FieldAdded {
+                        name: new.name.trim(),
+                    },
+                };
+                changes.push(change);
+            }
+        }
+        (Type::Enum(old), Type::Enum(new)) => {
+            changed(
+                &mut changes,
+                "headers",
+                !same_strs(old.headers, new.headers),
+            );
+            changed(
+                &mut changes,
+                "value_alias",
+                old.value_alias != new.value_alias,
+            );
+            changed(&mut changes, "reverse", old.reverse != new.reverse);
+
+            let same = |old: &Variant, new: &Variant| old.discriminant == new.discriminant;
+            for member in diff_members(old.variants, new.variants, |variant| variant.name, same) {
+                changes.push(match member {
+                    Member::Changed(old, new) => TypeChange::VariantValueChanged {
+                        name: new.name.trim(),
+                        from: old.discriminant,
+                        to: new.discriminant,
+                    },
+                    Member::Renamed(old, new) => TypeChange::VariantRenamed {
+                        from: old.name.trim(),
+                        to: new.name.trim(),
+                    },
+                    Member::Removed(old) => TypeChange::VariantRemoved {
+                        name: old.name.trim(),
+                    },
+                    Member::Added(new) => TypeChange::VariantAdded {
+                        name: new.name.trim(),
+                    },
+                });
+            }
+        }
+        (Type::Alias(old), Type::Alias(new)) => {
+            if old.ty.trim() != new.ty.trim() {
+                changes.push(TypeChange::AliasTypeChanged {
+                    from: old.ty.trim(),
+                    to: new.ty.trim(),
+                });
+            }
+        }
+        (Type::Builtin(..), Type::Builtin(..)) => {}
+        (old, new) => changes.push(TypeChange::KindChanged {
+            from: kind(old),
+            to: kind(new),
+        }),
+    }
+
+    changes
+}
+
+fn changed(changes: &mut Vec<TypeChange>, what: &'static str, changed: bool) {
+    if changed {
+        changes.push(TypeChange::Changed { what })
+    }
+}
+
+// the strings are compared like the names, ignoring the surrounding whitespace
+fn same_strs(old: &[&str], new: &[&str]) -> bool {
+    old.len() == new.len()
+        && old
+            .iter()
+            .zip(new)
+            .all(|(old, new)| old.trim() == new.trim())
+}
+
+// the changes to a field with the same name
+fn field_changes(old: &Field, new: &Field) -> impl Iterator<Item = TypeChange> {
+    let name = new.name.trim();
+    let ty = (old.ty.trim() != new.ty.trim()).then(|| TypeChange::FieldTypeChanged {
+        name,
+        from: old.ty.trim(),
+        to: new.ty.trim(),
+    });
+    let other = [
+        ("optional_key", old.optional_key != new.optional_key),
+        ("index", old.index != new.index),
+        ("positional", old.positional != new.positional),
+        ("cast", old.cast.map(str::trim) != new.cast.map(str::trim)),
+        ("inline", old.inline != new.inline),
+    ];
+    ty.into_iter().chain(
+        other
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(move |(what, _)| TypeChange::FieldChanged { name, what }),
+    )
+}
+
+const fn kind(ty: &Type) -> &'static str {
+    match ty {
+        Type::Class(..) => "class",
+        Type::Enum(..) => "enum",
+        Type::Alias(..) => "alias",
+        Type::Builtin(..) => "builtin",
+    }
+}
+
+enum Member<'a, T> {
+    Changed(&'a T, &'a T),
+    Renamed(&'a T, &'a T),
+    Removed(&'a T),
+    Added(&'a T),
+}
+
+// the removed and changed members in the old order, then the added members in the new order
+fn diff_members<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    name: impl Fn(&T) -> &'static str,
+    same: impl Fn(&T, &T) -> bool,
+) -> Vec<Member<'a, T>> {
+    let find = |list: &'a [T], item: &T| list.iter().find(|c| name(c).trim() == name(item).trim());
+
+    // neither name is in the other list, but everything else is the same
+    let renamed = |i: usize| match (old.get(i), new.get(i)) {
+        (Some(old_item), Some(new_item)) => {
+            find(new, old_item).is_none()
+                && find(old, new_item).is_none()
+                && same(old_item, new_item)
+        }
+        _ => false,
+    };
+
+    let mut members = Vec::new();
+    for (i, item) in old.iter().enumerate() {
+        match find(new, item) {
+            Some(new_item) if !same(item, new_item) => {
+                members.push(Member::Changed(item, new_item))
+            }
+            Some(..) => {}
+            None if renamed(i) => members.push(Member::Renamed(item, &new[i])),
+            None => members.push(Member::Removed(item)),
+        }
+    }
+
+    for (i, item) in new.iter().enumerate() {
+        if find(old, item).is_none() && !renamed(i) {
+            members.push(Member::Added(item));
+        }
+    }
+
+    members
+}
@@ -0,0 +1,884 @@
+use alloc::{borrow::Cow, collections::BTreeSet, format, string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+    lua_type::split_top_level, Alias, Anno, Class, Discriminant, Enum, Field, GenerateOptions,
+    Registry, Type, ValueAlias,
+};
+
+/// Generate [LuaLS](https://github.com/LuaLS/lua-language-server) compatible annotations for this [`type`](Anno)
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate<T>(out: &mut impl std::io::Write) -> std::io::Result<()>
+where
+    T: Anno,
+{
+    generate_type(out, &T::lua_type())
+}
+
+/// Generate [LuaLS](https://github.com/LuaLS/lua-language-server) compatible annotations for this [`type`](Anno) using the provided [`options`](GenerateOptions)
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_with<T>(
+    out: &mut impl std::io::Write,
+    options: &GenerateOptions,
+) -> std::io::Result<()>
+where
+    T: Anno,
+{
+    generate_type_with(out, &T::lua_type(), options)
+}
+
+/// Generate this [`type`](Anno), and every type it references
+///
+/// See [`Registry`] for details
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_with_dependencies<T>(out: &mut impl std::io::Write) -> std::io::Result<()>
+where
+    T: Anno + ?Sized,
+{
+    IoAdapter::wrap(out, generate_with_dependencies_fmt::<T>)
+}
+
+/// Generate a specific type
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_type(out: &mut impl std::io::Write, ty: &Type) -> std::io::Result<()> {
+    IoAdapter::wrap(out, |out| generate_type_fmt(out, ty))
+}
+
+/// Generate a specific type using the provided [`options`](GenerateOptions)
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_type_with(
+    out: &mut impl std::io::Write,
+    ty: &Type,
+    options: &GenerateOptions,
+) -> std::io::Result<()> {
+    IoAdapter::wrap(out, |out| generate_type_fmt_with(out, ty, options))
+}
+
+/// Generate a module containing all of the provided types
+///
+/// The types are assigned into a local table named `module` instead of globals, and
+/// the module is returned at the end so the output can be `require`d directly
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_module(
+    out: &mut impl std::io::Write,
+    module: &str,
+    types: &[Type],
+) -> std::io::Result<()> {
+    IoAdapter::wrap(out, |out| generate_module_fmt(out, module, types))
+}
+
+/// Generate a module containing all of the provided types using the provided [`options`](GenerateOptions)
+///
+/// See [`generate_module`] for details
+///
+/// This'll append to the writer passed into it
+///
+/// With [`GenerateOptions::strict_refs`] this fails with [`InvalidData`](std::io::ErrorKind::InvalidData)
+/// and the errors from [`validate_all`](crate::validate_all), without writing anything
+#[cfg(feature = "std")]
+pub fn generate_module_with(
+    out: &mut impl std::io::Write,
+    module: &str,
+    types: &[Type],
+    options: &GenerateOptions,
+) -> std::io::Result<()> {
+    if options.strict_refs {
+        if let Err(errors) = crate::validate_all(types) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                errors.join("\n"),
+            ));
+        }
+    }
+
+    IoAdapter::wrap(out, |out| {
+        generate_module_fmt_with(out, module, types, options)
+    })
+}
+
+/// Generate each type as it is yielded, skipping types with a name that was already generated
+///
+/// Only the names are kept around, so this works for very large sets of types,
+/// e.g. from a build script
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_stream(
+    out: &mut impl std::io::Write,
+    types: impl IntoIterator<Item = Type>,
+) -> std::io::Result<()> {
+    IoAdapter::wrap(out, |out| generate_stream_fmt(out, types))
+}
+
+/// Generate each type into its own `<name>.lua` file in `dir`
+///
+/// [`Builtin`](Type::Builtin) types are skipped
+///
+/// The directory is created if it doesn't exist, and existing files are overwritten.
+/// Characters in the name that aren't valid in a file name are replaced with `_`
+///
+/// This'll fail without writing anything if two types would be written to the same file
+#[cfg(feature = "std")]
+pub fn generate_each_to_dir(
+    dir: impl AsRef<std::path::Path>,
+    types: &[Type],
+) -> std::io::Result<()> {
+    let dir = dir.as_ref();
+
+    let mut files = alloc::collections::BTreeMap::new();
+    for ty in types.iter().filter(|ty| !matches!(ty, Type::Builtin(..))) {
+        let file_name = format!("{}.lua", sanitize_file_name(ty.name()));
+        if let Some(prev) = files.insert(file_name, ty) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "`{}` and `{}` would both be written to the same file",
+                    prev.name(),
+                    ty.name()
+                ),
+            ));
+        }
+    }
+
+    std::fs::create_dir_all(dir)?;
+    for (file_name, ty) in files {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(dir.join(file_name))?);
+        generate_type(&mut file, ty)?;
+        std::io::Write::flush(&mut file)?;
+    }
+    Ok(())
+}
+
+/// Generate a specific class
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_class(out: &mut impl std::io::Write, class: &Class) -> std::io::Result<()> {
+    IoAdapter::wrap(out, |out| generate_class_fmt(out, class))
+}
+
+/// Generate a specific enum
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_enum(out: &mut impl std::io::Write, enum_: &Enum) -> std::io::Result<()> {
+    IoAdapter::wrap(out, |out| generate_enum_fmt(out, enum_))
+}
+
+/// Generate a specific alias
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "std")]
+pub fn generate_alias(out: &mut impl std::io::Write, alias: &Alias) -> std::io::Result<()> {
+    IoAdapter::wrap(out, |out| generate_alias_fmt(out, alias))
+}
+
+/// Generate [LuaLS](https://github.com/LuaLS/lua-language-server) compatible annotations for this [`type`](Anno)
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_fmt<T>(out: &mut impl Write) -> core::fmt::Result
+where
+    T: Anno,
+{
+    generate_type_fmt(out, &T::lua_type())
+}
+
+/// Generate [LuaLS](https://github.com/LuaLS/lua-language-server) compatible annotations for this [`type`](Anno) using the provided [`options`](GenerateOptions)
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_fmt_with<T>(out: &mut impl Write, options: &GenerateOptions) -> core::fmt::Result
+where
+    T: Anno,
+{
+    generate_type_fmt_with(out, &T::lua_type(), options)
+}
+
+/// Generate this [`type`](Anno), and every type it references
+///
+/// See [`Registry`] for details
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_with_dependencies_fmt<T>(out: &mut impl Write) -> core::fmt::Result
+where
+    T: Anno + ?Sized,
+{
+    let mut registry = Registry::new();
+    registry.register::<T>();
+
+    let options = GenerateOptions::new();
+    let cx = Context::new("", &options);
+    for ty in registry.types() {
+        generate_type_in(out, &cx, ty)?;
+    }
+    Ok(())
+}
+
+/// Generate a specific type
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_type_fmt(out: &mut impl Write, ty: &Type) -> core::fmt::Result {
+    generate_type_fmt_with(out, ty, &GenerateOptions::new())
+}
+
+/// Generate a specific type using the provided [`options`](GenerateOptions)
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_type_fmt_with(
+    out: &mut impl Write,
+    ty: &Type,
+    options: &GenerateOptions,
+) -> core::fmt::Result {
+    generate_type_in(out, &Context::new("", options), ty)
+}
+
+/// Generate a module containing all of the provided types
+///
+/// See [`generate_module`] for details
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_module_fmt(
+    out: &mut impl Write,
+    module: &str,
+    types: &[Type],
+) -> core::fmt::Result {
+    generate_module_fmt_with(out, module, types, &GenerateOptions::new())
+}
+
+/// Generate a module containing all of the provided types using the provided [`options`](GenerateOptions)
+///
+/// See [`generate_module`] for details
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+///
+/// With [`GenerateOptions::strict_refs`] this fails without writing anything if [`validate_all`](crate::validate_all) fails,
+/// use that directly to get the errors
+pub fn generate_module_fmt_with(
+    out: &mut impl Write,
+    module: &str,
+    types: &[Type],
+    options: &GenerateOptions,
+) -> core::fmt::Result {
+    if options.strict_refs && crate::validate_all(types).is_err() {
+        return Err(core::fmt::Error);
+    }
+
+    let module = module.trim();
+    writeln!(out, "local {module} = {empty}", empty = options.empty_table)?;
+    writeln!(out)?;
+
+    let scope = format!("{module}.");
+    let cx = Context::new(&scope, options);
+    for ty in types {
+        generate_type_in(out, &cx, ty)?;
+    }
+
+    writeln!(out, "return {module}")
+}
+
+/// Generate each type as it is yielded, skipping types with a name that was already generated
+///
+/// See [`generate_stream`] for details
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_stream_fmt(
+    out: &mut impl Write,
+    types: impl IntoIterator<Item = Type>,
+) -> core::fmt::Result {
+    let options = GenerateOptions::new();
+    let cx = Context::new("", &options);
+
+    let mut seen = BTreeSet::new();
+    for ty in types {
+        if seen.insert(ty.name().trim()) {
+            generate_type_in(out, &cx, &ty)?;
+        }
+    }
+    Ok(())
+}
+
+/// Generate a specific class
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_class_fmt(out: &mut impl Write, class: &Class) -> core::fmt::Result {
+    generate_class_in(out, &Context::new("", &GenerateOptions::new()), class)
+}
+
+/// Generate a specific enum
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_enum_fmt(out: &mut impl Write, enum_: &Enum) -> core::fmt::Result {
+    generate_enum_in(out, &Context::new("", &GenerateOptions::new()), enum_)
+}
+
+/// Generate a specific alias
+///
+/// This'll append to the [`fmt::Write`](std::fmt::Write) passed into it, e.g. a [`String`]
+pub fn generate_alias_fmt(out: &mut impl Write, alias: &Alias) -> core::fmt::Result {
+    generate_alias_in(out, &Context::new("", &GenerateOptions::new()), alias)
+}
+
+// What is being generated into: the table prefix for type assignments (e.g. `M.`
+// in a module) and the options the generation was started with
+struct Context<'a> {
+    scope: &'a str,
+    options: &'a GenerateOptions,
+}
+
+impl<'a> Context<'a> {
+    const fn new(scope: &'a str, options: &'a GenerateOptions) -> Self {
+        Self { scope, options }
+    }
+}
+
+fn generate_type_in(out: &mut impl Write, cx: &Context, ty: &Type) -> core::fmt::Result {
+    match ty {
+        Type::Class(class) => generate_class_in(out, cx, class),
+        Type::Enum(enum_) => generate_enum_in(out, cx, enum_),
+        Type::Alias(alias) => generate_alias_in(out, cx, alias),
+        Type::Builtin(..) => Ok(()),
+    }
+}
+
+fn generate_alias_in(out: &mut impl Write, cx: &Context, alias: &Alias) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+    generate_docs(out, cx, "", alias.docs)?;
+    writeln!(
+        out,
+        "{prefix}@alias {name} {ty}",
+        name = alias.name.trim_start(),
+        ty = alias.ty.trim_start()
+    )?;
+    writeln!(out)
+}
+
+fn generate_class_in(out: &mut impl Write, cx: &Context, class: &Class) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+
+    let mut fields = Vec::new();
+    inline_fields(&mut fields, class.fields);
+
+    // the `---|` lines can only follow an `@alias`, so a long union gets its own alias before the class
+    let mut wrapped = BTreeSet::new();
+    for field in &fields {
+        let ty = self_type(class, field.ty);
+        let Some(variants) = wrapped_union(cx, field, &ty) else {
+            continue;
+        };
+        if !wrapped.insert(field.name.trim()) {
+            continue;
+        }
+        writeln!(
+            out,
+            "{prefix}@alias {name}",
+            name = union_alias_name(class, field)
+        )?;
+        for variant in variants {
+            writeln!(out, "{prefix}| {variant}", variant = variant.trim())?;
+        }
+        writeln!(out)?;
+    }
+
+    generate_docs(out, cx, "", class.docs)?;
+    generate_headers(out, cx, class.headers)?;
+
+    // an index signature allows any other key, so the class can't also be exact.
+    // the derive rejects this, but an inlined class can still bring one in
+    let index = fields.iter().find(|field| field.index);
+
+    write!(out, "{prefix}@class ")?;
+    if class.exact && cx.options.emit_exact && index.is_none() {
+        write!(out, "(exact) ")?;
+    }
+    write!(out, "{name}", name = class.name.trim_start())?;
+    if !class.generics.is_empty() {
+        write!(out, "<{generics}>", generics = class.generics.join(", "))?;
+    }
+    writeln!(out)?;
+
+    if let Some(field) = index.filter(|_| class.exact && cx.options.diagnostics) {
+        writeln!(
+            out,
+            "{comment} note: `(exact)` was dropped because of the index signature `[{name}]`",
+            comment = cx.options.comment_prefix,
+            name = field.name.trim()
+        )?;
+    }
+
+    for operator in class.operators {
+        write!(out, "{prefix}@operator {name}", name = operator.name.trim())?;
+        if let Some(rhs) = operator.rhs {
+            write!(out, "({rhs})", rhs = self_type(class, rhs).trim())?;
+        }
+        writeln!(
+            out,
+            ": {result}",
+            result = self_type(class, operator.result).trim()
+        )?;
+    }
+
+    // a stable partition, so each group keeps its declaration order
+    let required = |field: &&&Field| !cx.options.required_first || !is_optional(field);
+    let optional = |field: &&&Field| cx.options.required_first && is_optional(field);
+    let fields = fields
+        .iter()
+        .filter(required)
+        .chain(fields.iter().filter(optional));
+
+    let mut seen = BTreeSet::new();
+    for field in fields {
+        // the derive can only check the names of its own fields, an inlined duplicate is skipped
+        if !seen.insert((field.index || field.positional, field.name.trim())) {
+            if cx.options.diagnostics {
+                writeln!(
+                    out,
+                    "{comment} note: skipped a duplicate field `{name}`",
+                    comment = cx.options.comment_prefix,
+                    name = field.name.trim()
+                )?;
+            }
+            continue;
+        }
+        if cx.options.field_spacing && !field.docs.is_empty() {
+            writeln!(out)?;
+        }
+        generate_docs(out, cx, "", field.docs)?;
+        generate_field(out, cx, class, field, &self_type(class, field.ty))?;
+        if let Some(cast) = field.cast {
+            writeln!(out, "{prefix}{cast}", cast = cast.trim())?;
+        }
+    }
+
+    for method in class.methods {
+        writeln!(
+            out,
+            "{prefix}@field {name} {ty}",
+            name = method.name.trim_start(),
+            ty = self_type(class, method.ty).trim_start()
+        )?;
+    }
+
+    writeln!(
+        out,
+        "{scope}{name} = {empty}",
+        empty = cx.options.empty_table,
+        scope = cx.scope,
+        name = class.name.trim_start()
+    )?;
+    writeln!(out)
+}
+
+// the fields of an inlined class replace the field, its docs and `exact` aren't used
+pub(crate) fn inline_fields(out: &mut Vec<&'static Field>, fields: &'static [Field]) {
+    for field in fields {
+        match field.inline.map(|inline| inline.get()) {
+            Some(Type::Class(class)) => inline_fields(out, class.fields),
+            _ => out.push(field),
+        }
+    }
+}
+
+// replaces a `self` type with the name of the class, when the class asks for it.
+// `self` is a name rather than a type right after the `(` of a `fun(..)` or a `{`,
+// or after a `,` in either of those
+fn self_type(class: &Class, ty: &'static str) -> Cow<'static, str> {
+    if !class.self_refers_to_class || !ty.contains("self") {
+        return Cow::Borrowed(ty);
+    }
+
+    // whether each open bracket starts a list of names
+    let mut names = Vec::new();
+    let mut previous = None;
+    let mut word = String::new();
+    let mut out = String::with_capacity(ty.len());
+
+    for ch in ty.chars().chain(core::iter::once(' ')) {
+        if ch.is_alphanumeric() || ch == '_' || ch == '.' {
+            word.push(ch);
+            continue;
+        }
+
+        if !word.is_empty() {
+            let is_name = matches!(previous, Some('(' | '{' | ',')) && names.last() == Some(&true);
+            match &*word {
+                "self" if !is_name => out.push_str(class.name.trim()),
+                word => out.push_str(word),
+            }
+            previous = Some('a');
+        }
+
+        match ch {
+            '(' => names.push(word == "fun"),
+            '{' => names.push(true),
+            '[' | '<' => names.push(false),
+            ')' | '}' | ']' | '>' => drop(names.pop()),
+            _ => {}
+        }
+        if !ch.is_whitespace() {
+            previous = Some(ch);
+        }
+        word.clear();
+        out.push(ch);
+    }
+    out.pop();
+
+    Cow::Owned(out)
+}
+
+fn is_optional(field: &Field) -> bool {
+    field.optional_key || field.ty.trim().ends_with('?')
+}
+
+fn generate_headers(out: &mut impl Write, cx: &Context, headers: &[&str]) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+    for header in headers {
+        writeln!(out, "{prefix}{header}", header = header.trim())?;
+    }
+    Ok(())
+}
+
+// the `ty` is used instead of the type of the `field`, e.g. with `self` replaced
+fn generate_field(
+    out: &mut impl Write,
+    cx: &Context,
+    class: &Class,
+    field: &Field,
+    ty: &str,
+) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+    if field.index || field.positional {
+        writeln!(
+            out,
+            "{prefix}@field [{key}] {ty}",
+            key = field.name.trim(),
+            ty = ty.trim_start()
+        )?;
+        return Ok(());
+    }
+    let ty = match wrapped_union(cx, field, ty) {
+        Some(..) => Cow::Owned(union_alias_name(class, field)),
+        None => Cow::Borrowed(ty.trim()),
+    };
+    writeln!(
+        out,
+        "{prefix}@field {name}{optional} {ty}",
+        name = field.name.trim_start(),
+        optional = if field.optional_key { "?" } else { "" },
+    )
+}
+
+// the variants of a union that is longer than `union_wrap_width`
+fn wrapped_union<'a>(cx: &Context, field: &Field, ty: &'a str) -> Option<Vec<&'a str>> {
+    if field.index || field.positional {
+        return None;
+    }
+    let ty = ty.trim();
+    let variants = split_top_level(ty, '|');
+    let wrap = variants.len() > 1
+        && cx
+            .options
+            .union_wrap_width
+            .is_some_and(|width| ty.len() > width);
+    wrap.then_some(variants)
+}
+
+// e.g. `Compass_dir` for the `dir` field of `Compass`
+fn union_alias_name(class: &Class, field: &Field) -> String {
+    format!(
+        "{class}_{field}",
+        class = class.name.trim(),
+        field = field.name.trim()
+    )
+}
+
+fn generate_enum_in(out: &mut impl Write, cx: &Context, enum_: &Enum) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+    generate_docs(out, cx, "", enum_.docs)?;
+    generate_headers(out, cx, enum_.headers)?;
+
+    match enum_.value_alias {
+        ValueAlias::Replace => return generate_value_alias(out, cx, enum_.name, enum_),
+        ValueAlias::Literals => return generate_literal_alias(out, cx, enum_),
+        ValueAlias::None | ValueAlias::Named(..) => {}
+    }
+
+    writeln!(out, "{prefix}@enum {name}", name = enum_.name.trim_start())?;
+    generate_enum_table(out, cx, enum_)?;
+    writeln!(out)?;
+
+    if enum_.reverse {
+        generate_reverse_table(out, cx, enum_)?;
+    }
+
+    if let ValueAlias::Named(name) = enum_.value_alias {
+        generate_value_alias(out, cx, name, enum_)?;
+    }
+    Ok(())
+}
+
+// an enum without any variants is an empty table
+fn generate_enum_table(out: &mut impl Write, cx: &Context, enum_: &Enum) -> core::fmt::Result {
+    if enum_.variants.is_empty() {
+        return writeln!(
+            out,
+            "{scope}{name} = {empty}",
+            empty = cx.options.empty_table,
+            scope = cx.scope,
+            name = enum_.name.trim_start()
+        );
+    }
+
+    writeln!(
+        out,
+        "{scope}{name} = {{",
+        scope = cx.scope,
+        name = enum_.name.trim_start()
+    )?;
+
+    let indent = cx.options.table_indent;
+
+    let mut seen = BTreeSet::new();
+    for (i, variant) in enum_.variants.iter().enumerate() {
+        let comma = separator(cx, i + 1 == enum_.variants.len());
+        generate_docs(out, cx, indent, variant.docs)?;
+        write!(out, "{indent}{name} = ", name = variant.name.trim_start())?;
+        match variant.discriminant {
+            Discriminant::Number(n) => {
+                write!(out, "{n}{comma}")?;
+                // aliasing is allowed, but it is usually a mistake
+                if !seen.insert(n) && cx.options.diagnostics {
+                    write!(
+                        out,
+                        " {comment} note: duplicate discriminant",
+                        comment = cx.options.comment_prefix
+                    )?;
+                }
+                writeln!(out)?
+            }
+            Discriminant::Named(n) if cx.options.quote_named_discriminants => {
+                generate_string(out, n)?;
+                writeln!(out, "{comma}")?
+            }
+            Discriminant::Named(n) => writeln!(out, "{n}{comma}")?,
+            Discriminant::String(s) => {
+                generate_string(out, s)?;
+                writeln!(out, "{comma}")?
+            }
+        }
+    }
+    writeln!(out, "}}")
+}
+
+// the last entry of a table only has a comma with `trailing_comma`
+const fn separator(cx: &Context, last: bool) -> &'static str {
+    match last && !cx.options.trailing_comma {
+        true => "",
+        false => ",",
+    }
+}
+
+// only numeric discriminants can be reversed, the first variant wins for duplicates
+fn generate_reverse_table(out: &mut impl Write, cx: &Context, enum_: &Enum) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+
+    let mut seen = BTreeSet::new();
+    let values = enum_
+        .variants
+        .iter()
+        .filter_map(|variant| match variant.discriminant {
+            Discriminant::Number(n) => Some((n, variant.name)),
+            _ => None,
+        })
+        .filter(|(n, _)| seen.insert(*n))
+        .collect::<Vec<_>>();
+
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "{prefix}@type table<integer, string>")?;
+    writeln!(
+        out,
+        "{scope}{name}_reverse = {{",
+        scope = cx.scope,
+        name = enum_.name.trim_start()
+    )?;
+    for (i, (n, name)) in values.iter().enumerate() {
+        write!(out, "{indent}[{n}] = ", indent = cx.options.table_indent)?;
+        generate_string(out, name.trim_start())?;
+        writeln!(out, "{comma}", comma = separator(cx, i + 1 == values.len()))?;
+    }
+    writeln!(out, "}}")?;
+    writeln!(out)
+}
+
+fn generate_value_alias(
+    out: &mut impl Write,
+    cx: &Context,
+    name: &str,
+    enum_: &Enum,
+) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+
+    let mut types = Vec::new();
+    for variant in enum_.variants {
+        let ty = match variant.discriminant {
+            Discriminant::Number(..) => "integer",
+            Discriminant::String(..) => "string",
+            Discriminant::Named(name) => name,
+        };
+        if !types.contains(&ty) {
+            types.push(ty);
+        }
+    }
+    if types.is_empty() {
+        types.push("integer");
+    }
+
+    writeln!(
+        out,
+        "{prefix}@alias {name} {ty}",
+        name = name.trim_start(),
+        ty = types.join(" | ")
+    )?;
+    writeln!(out)
+}
+
+// each value is written once, with the name (and docs) of the first variant that has it
+fn generate_literal_alias(out: &mut impl Write, cx: &Context, enum_: &Enum) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+
+    writeln!(out, "{prefix}@alias {name}", name = enum_.name.trim_start())?;
+
+    let mut seen = BTreeSet::new();
+    for variant in enum_.variants {
+        if !seen.insert(variant.discriminant) {
+            continue;
+        }
+
+        write!(out, "{prefix}| ")?;
+        match variant.discriminant {
+            Discriminant::Number(n) => write!(out, "{n}")?,
+            Discriminant::Named(n) if cx.options.quote_named_discriminants => {
+                generate_string(out, n)?
+            }
+            Discriminant::Named(n) => write!(out, "{n}")?,
+            Discriminant::String(s) => generate_string(out, s)?,
+        }
+        write!(out, " # {name}", name = variant.name.trim())?;
+
+        let mut docs = variant
+            .docs
+            .iter()
+            .flat_map(|doc| doc.split('\n'))
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+        if let Some(first) = docs.next() {
+            write!(out, ": {first}")?;
+            for line in docs {
+                write!(out, " {line}")?;
+            }
+        }
+        writeln!(out)?;
+    }
+    writeln!(out)
+}
+
+// Docs are written one line at a time so a doc containing line breaks (or a
+// stray `\r` from a CRLF source) can't break out of the comment. Lines inside of
+// a ``` fence keep their indentation
+fn generate_docs(
+    out: &mut impl Write,
+    cx: &Context,
+    indent: &str,
+    docs: &[&str],
+) -> core::fmt::Result {
+    let prefix = cx.options.annotation_prefix;
+    let mut in_fence = false;
+    for doc in docs {
+        for line in doc.split('\n') {
+            let line = line.replace('\r', "");
+            let doc = if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                line.trim_start()
+            } else if in_fence {
+                line.trim_end()
+            } else {
+                line.trim_start()
+            };
+            writeln!(out, "{indent}{prefix} {doc}")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+fn sanitize_file_name(name: &str) -> alloc::string::String {
+    name.trim()
+        .chars()
+        .map(|ch| match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' | '.' => ch,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn generate_string(out: &mut impl Write, s: &str) -> core::fmt::Result {
+    out.write_char('"')?;
+    for ch in s.chars() {
+        match ch {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            ch => out.write_char(ch)?,
+        }
+    }
+    out.write_char('"')
+}
+
+// Lets the `io::Write` generators share the `fmt::Write` implementation, keeping
+// the underlying `io::Error` around so it can be returned instead of `fmt::Error`
+#[cfg(feature = "std")]
+struct IoAdapter<'a, W> {
+    out: &'a mut W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: std::io::Write> IoAdapter<'a, W> {
+    fn wrap(
+        out: &'a mut W,
+        generate: impl FnOnce(&mut Self) -> core::fmt::Result,
+    ) -> std::io::Result<()> {
+        let mut this = Self { out, error: None };
+        match generate(&mut this) {
+            Ok(()) => Ok(()),
+            Err(core::fmt::Error) => Err(this
+                .error
+                .unwrap_or_else(|| std::io::Error::other("formatter error"))),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.out.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            core::fmt::Error
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{Anno, Type};
+
+macro_rules! builtin {
+    ($name:literal => $($ty:ty),* $(,)?) => {
+        $(
+            impl Anno for $ty {
+                fn lua_type() -> Type {
+                    Type::Builtin($name)
+                }
+            }
+        )*
+    };
+}
+
+builtin!("boolean" => bool);
+builtin!("number" => f32, f64);
+builtin!("string" => str, char);
+builtin!("nil" => ());
+
+builtin!("integer" =>
+    i8, i16, i32, i64, isize,
+    u8, u16, u32, u64, usize,
+);
+
+builtin!("integer" =>
+    core::num::NonZeroI8, core::num::NonZeroI16, core::num::NonZeroI32,
+    core::num::NonZeroI64, core::num::NonZeroIsize,
+    core::num::NonZeroU8, core::num::NonZeroU16, core::num::NonZeroU32,
+    core::num::NonZeroU64, core::num::NonZeroUsize,
+);
+
+#[cfg(feature = "alloc")]
+builtin!("string" => alloc::string::String);
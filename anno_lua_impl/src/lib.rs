@@ -1,9 +1,25 @@
-use std::io::Write;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 /// Exposes a lua-generated type definition for this type
 pub trait Anno {
     /// Get a static definition of this type
     fn lua_type() -> Type;
+
+    /// Visit the other `Anno` types this type references
+    ///
+    /// The derive visits the fields marked with `#[anno(reference)]`
+    fn dependencies(visitor: &mut dyn Visitor) {
+        let _ = visitor;
+    }
+}
+
+/// Visits the dependencies of an [`Anno`] type, e.g. a [`Registry`]
+pub trait Visitor {
+    /// Visit a type, `dependencies` is that type's [`Anno::dependencies`]
+    fn visit(&mut self, ty: Type, dependencies: fn(&mut dyn Visitor));
 }
 
 /// Variant mapping of the lua named variants to the enum type
@@ -11,14 +27,44 @@ pub trait AnnoEnum: Anno + Sized + 'static {
     /// Get the variant mappings
     fn variants() -> &'static [(&'static str, Self)];
 
+    /// Get just the lua named variants, in the same order as [`AnnoEnum::variants`]
+    fn all_names() -> impl ExactSizeIterator<Item = &'static str> + Clone {
+        Self::variants().iter().map(|(name, _)| *name)
+    }
+
+    /// Get the lua named variants paired with their numeric discriminants
+    ///
+    /// Variants with a named (e.g. `self` or `alias` enums) or string discriminant are skipped
+    fn variant_values() -> impl Iterator<Item = (&'static str, i64)> + Clone {
+        let variants = match Self::lua_type() {
+            Type::Enum(enum_) => enum_.variants,
+            _ => &[],
+        };
+        variants
+            .iter()
+            .filter_map(|variant| match variant.discriminant {
+                Discriminant::Number(n) => Some((variant.name, n as i64)),
+                Discriminant::Named(..) | Discriminant::String(..) => None,
+            })
+    }
+
     /// Get the variant name
+    ///
+    /// An ignored variant isn't in the `@enum`, so it has no lua name and this is empty (`""`).
+    /// Check for that before using it as a key, or only use the variants from [`AnnoEnum::variants`]
     fn variant_name(&self) -> &'static str;
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Type {
     Class(Class),
     Enum(Enum),
+    Alias(Alias),
+    /// A type LuaLS already knows about, e.g. `integer` or `string`
+    ///
+    /// Nothing is generated for these
+    Builtin(&'static str),
 }
 
 impl Type {
@@ -26,114 +72,409 @@ impl Type {
         match self {
             Self::Class(c) => c.name,
             Self::Enum(e) => e.name,
+            Self::Alias(a) => a.name,
+            Self::Builtin(name) => name,
         }
     }
+
+    pub const fn is_exact(&self) -> bool {
+        match self {
+            Self::Class(c) => c.is_exact(),
+            Self::Enum(..) | Self::Alias(..) | Self::Builtin(..) => false,
+        }
+    }
+
+    /// Generate the annotations for this type into a new `String`
+    ///
+    /// This is the same as calling [`generate_type_fmt`] with an empty `String`
+    #[cfg(feature = "alloc")]
+    pub fn to_lua_string(&self) -> alloc::string::String {
+        let mut out = alloc::string::String::new();
+        // writing to a `String` cannot fail
+        let _ = generate_type_fmt(&mut out, self);
+        out
+    }
+
+    /// An indented outline of this type, its fields or variants and their docs
+    ///
+    /// This is meant for debugging, the format isn't stable
+    #[cfg(feature = "alloc")]
+    pub fn debug_tree(&self) -> alloc::string::String {
+        let mut out = alloc::string::String::new();
+        // writing to a `String` cannot fail
+        let _ = debug_tree::debug_tree(&mut out, self);
+        out
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Class {
     pub exact: bool,
+    /// Raw annotations written before the `@class`, e.g. `@diagnostic disable`
+    pub headers: &'static [&'static str],
     pub docs: &'static [&'static str],
     pub name: &'static str,
+    pub generics: &'static [&'static str],
     pub fields: &'static [Field],
+    /// Methods exposed on the type, emitted after the fields
+    pub methods: &'static [Method],
+    /// Operator metamethods of the type, emitted after the `@class`
+    pub operators: &'static [Operator],
+    /// Whether a `self` type in the fields and methods is replaced with the name of the class,
+    /// e.g. `fun(self): self` is `fun(self): Vec2`. A parameter named `self` is kept
+    pub self_refers_to_class: bool,
+}
+
+impl Class {
+    /// Create a new class with no docs, generics or methods that isn't `exact`
+    pub const fn new(name: &'static str, fields: &'static [Field]) -> Self {
+        Self {
+            exact: false,
+            headers: &[],
+            docs: &[],
+            name,
+            generics: &[],
+            fields,
+            methods: &[],
+            operators: &[],
+            self_refers_to_class: false,
+        }
+    }
+
+    pub const fn is_exact(&self) -> bool {
+        self.exact
+    }
+
+    /// Find a field by its lua name
+    pub fn field(&self, name: &str) -> Option<&'static Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Enum {
+    /// Raw annotations written before the `@enum`, e.g. `@diagnostic disable`
+    pub headers: &'static [&'static str],
     pub docs: &'static [&'static str],
     pub name: &'static str,
     pub variants: &'static [Variant],
+    /// Whether an `@alias` of the variant values is also emitted
+    pub value_alias: ValueAlias,
+    /// Whether a `Name_reverse` table mapping the numeric values back to the variant names is also emitted
+    pub reverse: bool,
+}
+
+impl Enum {
+    /// Create a new enum with no docs
+    pub const fn new(name: &'static str, variants: &'static [Variant]) -> Self {
+        Self {
+            headers: &[],
+            docs: &[],
+            name,
+            variants,
+            value_alias: ValueAlias::None,
+            reverse: false,
+        }
+    }
+
+    /// Find a variant by its lua name
+    pub fn variant(&self, name: &str) -> Option<&'static Variant> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+
+    /// Does any variant use a [named](Discriminant::Named) discriminant, e.g. a `self` or `alias` enum
+    pub fn is_self_referential(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| matches!(variant.discriminant, Discriminant::Named(..)))
+    }
+}
+
+/// An `@alias` for the values of an [`Enum`], e.g. `---@alias Direction integer`
+///
+/// The aliased type is `integer` for numeric discriminants, `string` for string discriminants
+/// and the type name for named discriminants
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ValueAlias {
+    /// Only the `@enum` is emitted
+    #[default]
+    None,
+    /// The `@alias` is emitted instead of the `@enum`, using the name of the enum
+    Replace,
+    /// The `@alias` is emitted after the `@enum`, using this name
+    Named(&'static str),
+    /// The `@alias` is a union of the variant values instead of their types, e.g. `---| 0 # Low`,
+    /// it is emitted instead of the `@enum`, using the name of the enum
+    Literals,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
-pub struct Field {
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Alias {
+    pub docs: &'static [&'static str],
     pub name: &'static str,
     pub ty: &'static str,
-    pub docs: &'static [&'static str],
+}
+
+impl Alias {
+    /// Create a new alias with no docs
+    pub const fn new(name: &'static str, ty: &'static str) -> Self {
+        Self {
+            docs: &[],
+            name,
+            ty,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
-pub struct Variant {
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Field {
     pub name: &'static str,
-    pub discriminant: Discriminant,
+    pub ty: &'static str,
+    /// Whether the key may be absent from the table, e.g. `---@field name? type`
+    pub optional_key: bool,
+    /// Whether the `name` is the key type of an index signature, e.g. `---@field [string] type`
+    pub index: bool,
+    /// Whether the `name` is a literal key, e.g. `---@field [1] type` for a tuple struct field
+    pub positional: bool,
     pub docs: &'static [&'static str],
+    /// The `file:line` this was declared at, when `#[anno(with_location)]` is used
+    pub location: Option<&'static str>,
+    /// A raw annotation written after the `@field`, e.g. `@as integer`
+    pub cast: Option<&'static str>,
+    /// The fields of this class are used in place of this field, when `#[anno(inline)]` is used
+    pub inline: Option<LuaTypeFn>,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
-pub enum Discriminant {
-    Number(isize),
-    Named(&'static str),
+impl Field {
+    /// Create a new field with no docs or location
+    pub const fn new(name: &'static str, ty: &'static str) -> Self {
+        Self {
+            name,
+            ty,
+            optional_key: false,
+            index: false,
+            positional: false,
+            docs: &[],
+            location: None,
+            cast: None,
+            inline: None,
+        }
+    }
 }
 
-/// Generate [LuaLS](https://github.com/LuaLS/lua-language-server) compatible annotations for this [`type`](Anno)
+/// The [`Anno::lua_type`] of another type, e.g. for an inlined [`Field`]
 ///
-/// This'll append to the writer passed into it
-pub fn generate<T>(out: &mut impl Write) -> std::io::Result<()>
-where
-    T: Anno,
-{
-    generate_type(out, &T::lua_type())
+/// This is compared by the [`Type`] it returns, rather than by the address of the function
+#[derive(Copy, Clone)]
+pub enum LuaTypeFn {
+    /// The function of the type, this is what the derive uses
+    Fn(fn() -> Type),
+    /// An already resolved type, e.g. one that was deserialized
+    Static(&'static Type),
 }
 
-/// Generate a specific type
-///
-/// This'll append to the writer passed into it
-pub fn generate_type(out: &mut impl Write, ty: &Type) -> std::io::Result<()> {
-    match ty {
-        Type::Class(class) => generate_class(out, &class),
-        Type::Enum(enum_) => generate_enum(out, &enum_),
+impl LuaTypeFn {
+    /// Get the [`Type`]
+    pub fn get(&self) -> Type {
+        match self {
+            Self::Fn(lua_type) => lua_type(),
+            Self::Static(ty) => **ty,
+        }
     }
 }
 
-/// Generate a specific class
-///
-/// This'll append to the writer passed into it
-pub fn generate_class(out: &mut impl Write, class: &Class) -> std::io::Result<()> {
-    for doc in class.docs {
-        writeln!(out, "--- {doc}", doc = doc.trim_start())?;
+impl core::fmt::Debug for LuaTypeFn {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("LuaTypeFn")
+            .field(&self.get().name())
+            .finish()
     }
-    write!(out, "---@class ")?;
-    if class.exact {
-        write!(out, "(exact) ")?;
+}
+
+impl PartialEq for LuaTypeFn {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl Eq for LuaTypeFn {}
+
+impl PartialOrd for LuaTypeFn {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
     }
-    writeln!(out, "{name}", name = class.name.trim_start())?;
+}
 
-    for field in class.fields {
-        for doc in field.docs {
-            writeln!(out, "--- {doc}", doc = doc.trim_start())?;
+impl Ord for LuaTypeFn {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.get().cmp(&other.get())
+    }
+}
+
+impl core::hash::Hash for LuaTypeFn {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+/// A method signature, e.g. `greet` with `fun(self): string`
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Method {
+    pub name: &'static str,
+    pub ty: &'static str,
+}
+
+impl Method {
+    /// Create a new method
+    pub const fn new(name: &'static str, ty: &'static str) -> Self {
+        Self { name, ty }
+    }
+}
+
+/// An operator metamethod, e.g. `add` with `Vec2` is `---@operator add(Vec2): Vec2`
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Operator {
+    /// The name of the metamethod without the `__`, e.g. `add` or `unm`
+    pub name: &'static str,
+    /// The type of the right hand side, this is `None` for the unary operators, e.g. `unm` and `len`
+    pub rhs: Option<&'static str>,
+    pub result: &'static str,
+}
+
+impl Operator {
+    /// The operators LuaLS understands
+    pub const SUPPORTED: &'static [&'static str] = &[
+        "add", "sub", "mul", "div", "mod", "pow", "idiv", "unm", "concat", "len", "eq", "lt", "le",
+        "band", "bor", "bxor", "shl", "shr", "bnot", "call",
+    ];
+
+    /// The operators that don't have a right hand side
+    pub const UNARY: &'static [&'static str] = &["unm", "len", "bnot"];
+
+    /// Create a new binary operator
+    pub const fn new(name: &'static str, rhs: &'static str, result: &'static str) -> Self {
+        Self {
+            name,
+            rhs: Some(rhs),
+            result,
         }
-        writeln!(
-            out,
-            "---@field {name} {ty}",
-            name = field.name.trim_start(),
-            ty = field.ty.trim_start()
-        )?;
     }
 
-    writeln!(out, "{name} = {{ }}", name = class.name.trim_start())?;
-    writeln!(out)
+    /// Create a new unary operator
+    pub const fn unary(name: &'static str, result: &'static str) -> Self {
+        Self {
+            name,
+            rhs: None,
+            result,
+        }
+    }
 }
 
-/// Generate a specific enum
-///
-/// This'll append to the writer passed into it
-pub fn generate_enum(out: &mut impl Write, enum_: &Enum) -> std::io::Result<()> {
-    for doc in enum_.docs {
-        writeln!(out, "--- {doc}", doc = doc.trim_start())?;
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Variant {
+    pub name: &'static str,
+    pub discriminant: Discriminant,
+    pub docs: &'static [&'static str],
+    /// The `file:line` this was declared at, when `#[anno(with_location)]` is used
+    pub location: Option<&'static str>,
+}
+
+impl Variant {
+    /// Create a new variant with no docs or location
+    pub const fn new(name: &'static str, discriminant: Discriminant) -> Self {
+        Self {
+            name,
+            discriminant,
+            docs: &[],
+            location: None,
+        }
     }
 
-    writeln!(out, "---@enum {name}", name = enum_.name.trim_start())?;
-    writeln!(out, "{name} = {{", name = enum_.name.trim_start())?;
-    for variant in enum_.variants {
-        for doc in variant.docs {
-            writeln!(out, "    --- {doc}", doc = doc.trim_start())?;
+    /// Get the [numeric](Discriminant::Number) discriminant of this variant
+    ///
+    /// This is `None` for named and string discriminants
+    pub const fn numeric_value(&self) -> Option<i64> {
+        match self.discriminant {
+            Discriminant::Number(n) => Some(n as i64),
+            Discriminant::Named(..) | Discriminant::String(..) => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Discriminant {
+    Number(isize),
+    Named(&'static str),
+    String(&'static str),
+}
+
+#[cfg(feature = "alloc")]
+mod generate;
+#[cfg(feature = "alloc")]
+pub use generate::*;
+
+#[cfg(feature = "alloc")]
+mod lua_type;
+#[cfg(feature = "alloc")]
+pub use lua_type::LuaType;
+
+mod impls;
+
+#[cfg(feature = "alloc")]
+mod debug_tree;
+
+#[cfg(feature = "alloc")]
+mod validate;
+#[cfg(feature = "alloc")]
+pub use validate::{validate_all, validate_types, UnknownType};
+
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "alloc")]
+pub use diff::{diff_types, TypeChange};
+
+#[cfg(feature = "alloc")]
+mod registry;
+#[cfg(feature = "alloc")]
+pub use registry::Registry;
+
+mod options;
+pub use options::GenerateOptions;
+
+// used by the derive, not part of the public api
+#[doc(hidden)]
+pub mod __private {
+    /// `str` equality, usable in a const context
+    pub const fn str_eq(left: &str, right: &str) -> bool {
+        let (left, right) = (left.as_bytes(), right.as_bytes());
+        if left.len() != right.len() {
+            return false;
         }
-        write!(out, "    {name} = ", name = variant.name.trim_start())?;
-        match variant.discriminant {
-            Discriminant::Number(n) => writeln!(out, "{n},")?,
-            Discriminant::Named(n) => writeln!(out, "{n},")?,
+        let mut i = 0;
+        while i < left.len() {
+            if left[i] != right[i] {
+                return false;
+            }
+            i += 1;
         }
+        true
     }
-    writeln!(out, "}}")?;
-    writeln!(out)
 }
+
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "ron")]
+pub use serialize::generate_ron;
+#[cfg(feature = "toml")]
+pub use serialize::generate_toml;
+#[cfg(feature = "json")]
+pub use serialize::{generate_json, generate_module_json};
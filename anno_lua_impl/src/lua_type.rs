@@ -0,0 +1,186 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// A parsed lua type annotation, e.g. the `ty` of a [`Field`](crate::Field)
+///
+/// This only understands the shapes the derive produces, anything else is kept as a [`LuaType::Name`]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub enum LuaType {
+    /// A plain type, e.g. `integer` or `fun(self): string`
+    Name(String),
+    /// `T?`
+    Optional(Box<LuaType>),
+    /// `T[]`
+    Array(Box<LuaType>),
+    /// `table<K, V>`
+    Table(Box<LuaType>, Box<LuaType>),
+    /// `[A, B]`
+    Tuple(Vec<LuaType>),
+    /// `A | B`
+    Union(Vec<LuaType>),
+}
+
+impl LuaType {
+    /// Parse a lua type annotation
+    ///
+    /// This never fails, unrecognized types are kept as a [`LuaType::Name`]
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim();
+
+        let variants = split_top_level(s, '|');
+        if variants.len() > 1 {
+            return Self::Union(variants.into_iter().map(Self::parse).collect());
+        }
+
+        // the return type is the rest of the function, e.g. `fun(): integer[]` isn't an array
+        if is_function(s, 0) {
+            return Self::Name(s.into());
+        }
+
+        if let Some(inner) = s.strip_suffix('?') {
+            return Self::Optional(Box::new(Self::parse(inner)));
+        }
+
+        if let Some(inner) = s.strip_suffix("[]") {
+            return Self::Array(Box::new(Self::parse(inner)));
+        }
+
+        if let Some(inner) = strip_delimited(s, '(', ')') {
+            return Self::parse(inner);
+        }
+
+        if let Some(inner) = strip_delimited(s, '[', ']') {
+            let elements = split_top_level(inner, ',');
+            return Self::Tuple(elements.into_iter().map(Self::parse).collect());
+        }
+
+        if let Some(inner) = s
+            .strip_prefix("table")
+            .and_then(|s| strip_delimited(s, '<', '>'))
+        {
+            if let [key, value] = &*split_top_level(inner, ',') {
+                return Self::Table(Box::new(Self::parse(key)), Box::new(Self::parse(value)));
+            }
+        }
+
+        Self::Name(s.into())
+    }
+
+    /// Is this an [`LuaType::Optional`]
+    pub const fn is_optional(&self) -> bool {
+        matches!(self, Self::Optional(..))
+    }
+
+    /// Is this an [`LuaType::Array`]
+    pub const fn is_array(&self) -> bool {
+        matches!(self, Self::Array(..))
+    }
+
+    /// Is this an [`LuaType::Table`]
+    pub const fn is_table(&self) -> bool {
+        matches!(self, Self::Table(..))
+    }
+}
+
+impl core::str::FromStr for LuaType {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::parse(s))
+    }
+}
+
+impl core::fmt::Display for LuaType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // unions have to be wrapped when they are the inner type of a suffix
+        fn inner(f: &mut core::fmt::Formatter<'_>, ty: &LuaType) -> core::fmt::Result {
+            match ty {
+                LuaType::Union(..) => write!(f, "({ty})"),
+                _ => write!(f, "{ty}"),
+            }
+        }
+
+        fn list(
+            f: &mut core::fmt::Formatter<'_>,
+            types: &[LuaType],
+            sep: &str,
+        ) -> core::fmt::Result {
+            for (i, ty) in types.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(sep)?;
+                }
+                write!(f, "{ty}")?;
+            }
+            Ok(())
+        }
+
+        match self {
+            Self::Name(name) => f.write_str(name),
+            Self::Optional(ty) => {
+                inner(f, ty)?;
+                f.write_str("?")
+            }
+            Self::Array(ty) => {
+                inner(f, ty)?;
+                f.write_str("[]")
+            }
+            Self::Table(key, value) => write!(f, "table<{key}, {value}>"),
+            Self::Tuple(types) => {
+                f.write_str("[")?;
+                list(f, types, ", ")?;
+                f.write_str("]")
+            }
+            Self::Union(types) => list(f, types, " | "),
+        }
+    }
+}
+
+// strips `open` and `close` only if they match each other, e.g. `(a)|(b)` isn't `a)|(b`
+fn strip_delimited(s: &str, open: char, close: char) -> Option<&str> {
+    let inner = s.strip_prefix(open)?.strip_suffix(close)?;
+    let mut depth = 0_usize;
+    for ch in inner.chars() {
+        if ch == open {
+            depth += 1;
+        } else if ch == close {
+            depth = depth.checked_sub(1)?;
+        }
+    }
+    (depth == 0).then_some(inner)
+}
+
+// whether a `fun(` starts at `i`, and isn't the end of a longer name like `my_fun(`
+fn is_function(s: &str, i: usize) -> bool {
+    s[i..].starts_with("fun(")
+        && !s[..i]
+            .chars()
+            .next_back()
+            .is_some_and(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
+// splits on `sep` when it isn't nested in brackets or a string literal.
+// a function's return type isn't delimited, so nothing after a `fun(` is split
+pub(crate) fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut start = 0;
+
+    for (i, ch) in s.char_indices() {
+        match (quote, ch) {
+            (Some(q), ch) if ch == q => quote = None,
+            (Some(..), ..) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, 'f') if depth == 0 && is_function(s, i) => break,
+            (None, '(' | '[' | '<' | '{') => depth += 1,
+            (None, ')' | ']' | '>' | '}') => depth = depth.saturating_sub(1),
+            (None, ch) if ch == sep && depth == 0 => {
+                out.push(&s[start..i]);
+                start = i + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    out.push(&s[start..]);
+    out
+}
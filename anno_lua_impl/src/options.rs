@@ -0,0 +1,61 @@
+/// Options controlling how annotations are generated
+///
+/// The default options produce the same output as the plain `generate` functions
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GenerateOptions {
+    /// Add `-- note:` comments for things that are valid but possibly unintended,
+    /// e.g. enum variants sharing a numeric discriminant
+    pub diagnostics: bool,
+    /// Emit the `(exact)` marker for [`exact`](crate::Class::exact) classes, this is on by default
+    pub emit_exact: bool,
+    /// Field types that are unions longer than this are written as an `@alias` before the class,
+    /// with a `---|` line per variant, and the field uses that alias, e.g. `Class_field`
+    pub union_wrap_width: Option<usize>,
+    /// Emit the required fields of a class before the optional ones (`name? type` or `name type?`),
+    /// otherwise fields are in declaration order
+    pub required_first: bool,
+    /// Write a blank line before each field that has docs, otherwise the fields are written without any spacing
+    pub field_spacing: bool,
+    /// Check the types with [`validate_all`](crate::validate_all) before generating a module,
+    /// so a misspelled type name is an error rather than a dangling reference
+    pub strict_refs: bool,
+    /// Write [named](crate::Discriminant::Named) discriminants as strings, e.g. `Up = "Dir",`,
+    /// otherwise they are a reference, e.g. `Up = Dir,`
+    pub quote_named_discriminants: bool,
+    /// Write a comma after the last entry of an enum (or its reverse) table, e.g. `Down = 1,`, this is on by default
+    pub trailing_comma: bool,
+    /// The prefix for annotations and doc comments, `---` by default
+    pub annotation_prefix: &'static str,
+    /// The prefix for plain comments, `--` by default
+    pub comment_prefix: &'static str,
+    /// How an empty table is written, e.g. `Name = { }` for a class or an enum without any variants, `{ }` by default
+    pub empty_table: &'static str,
+    /// The indentation of the entries of an enum (or its reverse) table, four spaces by default
+    pub table_indent: &'static str,
+}
+
+impl GenerateOptions {
+    /// Create the default options
+    pub const fn new() -> Self {
+        Self {
+            diagnostics: false,
+            emit_exact: true,
+            union_wrap_width: None,
+            required_first: false,
+            field_spacing: false,
+            strict_refs: false,
+            quote_named_discriminants: false,
+            trailing_comma: true,
+            annotation_prefix: "---",
+            comment_prefix: "--",
+            empty_table: "{ }",
+            table_indent: "    ",
+        }
+    }
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
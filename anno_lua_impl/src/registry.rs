@@ -0,0 +1,48 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use crate::{Anno, Type, Visitor};
+
+/// A collection of types and all of the types they reference
+///
+/// Each type is only added once, in the order they were first seen.
+/// [`Builtin`](Type::Builtin) types are skipped
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    seen: BTreeSet<Type>,
+    types: Vec<Type>,
+}
+
+impl Registry {
+    /// Create an empty registry
+    pub const fn new() -> Self {
+        Self {
+            seen: BTreeSet::new(),
+            types: Vec::new(),
+        }
+    }
+
+    /// Add this type, and every type it references
+    pub fn register<T>(&mut self) -> &mut Self
+    where
+        T: Anno + ?Sized,
+    {
+        self.visit(T::lua_type(), T::dependencies);
+        self
+    }
+
+    /// Get the registered types
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+}
+
+impl Visitor for Registry {
+    fn visit(&mut self, ty: Type, dependencies: fn(&mut dyn Visitor)) {
+        // the type is added before its dependencies so recursive types terminate
+        if matches!(ty, Type::Builtin(..)) || !self.seen.insert(ty) {
+            return;
+        }
+        self.types.push(ty);
+        dependencies(self)
+    }
+}
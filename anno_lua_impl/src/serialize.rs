@@ -0,0 +1,251 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    Alias, Class, Discriminant, Enum, Field, LuaTypeFn, Method, Operator, Type, ValueAlias, Variant,
+};
+
+// an inlined type is written out, the function itself doesn't mean anything outside of this process
+impl serde::Serialize for LuaTypeFn {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+// the types only borrow `'static` data, so they are deserialized into the owned types below and then leaked.
+// this is fine for loading a snapshot of the types once, but not for doing it in a loop
+
+fn leak_str(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+fn leak_strs(list: Vec<String>) -> &'static [&'static str] {
+    leak(list.into_iter().map(leak_str).collect())
+}
+
+fn leak<T>(list: Vec<T>) -> &'static [T] {
+    Box::leak(list.into_boxed_slice())
+}
+
+// these mirror the layout of the borrowed types, so they read what `Serialize` writes
+mod owned {
+    use alloc::{string::String, vec::Vec};
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub enum Type {
+        Class(crate::Class),
+        Enum(crate::Enum),
+        Alias(crate::Alias),
+        Builtin(String),
+    }
+
+    #[derive(Deserialize)]
+    pub struct Class {
+        pub exact: bool,
+        pub headers: Vec<String>,
+        pub docs: Vec<String>,
+        pub name: String,
+        pub generics: Vec<String>,
+        pub fields: Vec<crate::Field>,
+        pub methods: Vec<crate::Method>,
+        pub operators: Vec<crate::Operator>,
+        pub self_refers_to_class: bool,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Enum {
+        pub headers: Vec<String>,
+        pub docs: Vec<String>,
+        pub name: String,
+        pub variants: Vec<crate::Variant>,
+        pub value_alias: crate::ValueAlias,
+        pub reverse: bool,
+    }
+
+    #[derive(Deserialize)]
+    pub enum ValueAlias {
+        None,
+        Replace,
+        Named(String),
+        Literals,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Alias {
+        pub docs: Vec<String>,
+        pub name: String,
+        pub ty: String,
+    }
+
+    // `inline` is the type that was written out, not the function
+    #[derive(Deserialize)]
+    pub struct Field {
+        pub name: String,
+        pub ty: String,
+        pub optional_key: bool,
+        pub index: bool,
+        pub positional: bool,
+        pub docs: Vec<String>,
+        pub location: Option<String>,
+        pub cast: Option<String>,
+        pub inline: Option<crate::Type>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Method {
+        pub name: String,
+        pub ty: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Operator {
+        pub name: String,
+        pub rhs: Option<String>,
+        pub result: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Variant {
+        pub name: String,
+        pub discriminant: crate::Discriminant,
+        pub docs: Vec<String>,
+        pub location: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub enum Discriminant {
+        Number(isize),
+        Named(String),
+        String(String),
+    }
+}
+
+macro_rules! deserialize {
+    ($($ty:ident => |$owned:ident| $leak:expr;)*) => {
+        $(
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+                    owned::$ty::deserialize(de).map(|$owned| $leak)
+                }
+            }
+        )*
+    };
+}
+
+deserialize! {
+    Type => |ty| match ty {
+        owned::Type::Class(class) => Self::Class(class),
+        owned::Type::Enum(enum_) => Self::Enum(enum_),
+        owned::Type::Alias(alias) => Self::Alias(alias),
+        owned::Type::Builtin(name) => Self::Builtin(leak_str(name)),
+    };
+    Class => |class| Self {
+        exact: class.exact,
+        headers: leak_strs(class.headers),
+        docs: leak_strs(class.docs),
+        name: leak_str(class.name),
+        generics: leak_strs(class.generics),
+        fields: leak(class.fields),
+        methods: leak(class.methods),
+        operators: leak(class.operators),
+        self_refers_to_class: class.self_refers_to_class,
+    };
+    Enum => |enum_| Self {
+        headers: leak_strs(enum_.headers),
+        docs: leak_strs(enum_.docs),
+        name: leak_str(enum_.name),
+        variants: leak(enum_.variants),
+        value_alias: enum_.value_alias,
+        reverse: enum_.reverse,
+    };
+    Alias => |alias| Self {
+        docs: leak_strs(alias.docs),
+        name: leak_str(alias.name),
+        ty: leak_str(alias.ty),
+    };
+    Field => |field| Self {
+        name: leak_str(field.name),
+        ty: leak_str(field.ty),
+        optional_key: field.optional_key,
+        index: field.index,
+        positional: field.positional,
+        docs: leak_strs(field.docs),
+        location: field.location.map(leak_str),
+        cast: field.cast.map(leak_str),
+        inline: field.inline.map(|ty| LuaTypeFn::Static(Box::leak(Box::new(ty)))),
+    };
+    Method => |method| Self {
+        name: leak_str(method.name),
+        ty: leak_str(method.ty),
+    };
+    Operator => |operator| Self {
+        name: leak_str(operator.name),
+        rhs: operator.rhs.map(leak_str),
+        result: leak_str(operator.result),
+    };
+    Variant => |variant| Self {
+        name: leak_str(variant.name),
+        discriminant: variant.discriminant,
+        docs: leak_strs(variant.docs),
+        location: variant.location.map(leak_str),
+    };
+    ValueAlias => |alias| match alias {
+        owned::ValueAlias::None => Self::None,
+        owned::ValueAlias::Replace => Self::Replace,
+        owned::ValueAlias::Named(name) => Self::Named(leak_str(name)),
+        owned::ValueAlias::Literals => Self::Literals,
+    };
+    Discriminant => |discriminant| match discriminant {
+        owned::Discriminant::Number(n) => Self::Number(n),
+        owned::Discriminant::Named(name) => Self::Named(leak_str(name)),
+        owned::Discriminant::String(s) => Self::String(leak_str(s)),
+    };
+}
+
+/// Generate the json for a [`Type`]
+///
+/// The json has the same layout as the types in this crate,
+/// e.g. `{"Class": {"name": "Foo", "fields": [...], ...}}`
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "json")]
+pub fn generate_json(out: &mut impl std::io::Write, ty: &Type) -> std::io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, ty)?;
+    writeln!(out)
+}
+
+/// Generate the json for several [`Type`]s, as an array
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "json")]
+pub fn generate_module_json(out: &mut impl std::io::Write, types: &[Type]) -> std::io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, types)?;
+    writeln!(out)
+}
+
+/// Generate the [ron](https://github.com/ron-rs/ron) for a [`Type`]
+///
+/// This has the same layout as the json, see [`generate_json`]
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "ron")]
+pub fn generate_ron(out: &mut impl std::io::Write, ty: &Type) -> std::io::Result<()> {
+    let ron = ron::ser::to_string_pretty(ty, ron::ser::PrettyConfig::default())
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+    writeln!(out, "{ron}")
+}
+
+/// Generate the [toml](https://toml.io) for a [`Type`]
+///
+/// This has the same layout as the json, see [`generate_json`]
+///
+/// This'll append to the writer passed into it
+#[cfg(feature = "toml")]
+pub fn generate_toml(out: &mut impl std::io::Write, ty: &Type) -> std::io::Result<()> {
+    let toml = toml::to_string_pretty(ty)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+    write!(out, "{toml}")
+}
@@ -0,0 +1,262 @@
+use alloc::{collections::BTreeSet, format, string::String, vec::Vec};
+
+use crate::{generate::inline_fields, lua_type::split_top_level, LuaType, Type, ValueAlias};
+
+// the types LuaLS knows about without them being declared
+const PRIMITIVES: &[&str] = &[
+    "nil",
+    "any",
+    "unknown",
+    "boolean",
+    "true",
+    "false",
+    "string",
+    "number",
+    "integer",
+    "function",
+    "table",
+    "thread",
+    "userdata",
+    "lightuserdata",
+    "self",
+];
+
+/// A type referenced by a field or alias that wasn't found by [`validate_types`]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct UnknownType {
+    /// The lua name of the class or alias with the reference
+    pub ty: &'static str,
+    /// The field, method or operator with the reference, this is `None` for an alias
+    pub field: Option<&'static str>,
+    /// The unknown type, e.g. `Usr` for a field of `Usr[]`
+    pub name: String,
+}
+
+impl core::fmt::Display for UnknownType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.field {
+            Some(field) => write!(f, "`{}.{field}`", self.ty)?,
+            None => write!(f, "`{}`", self.ty)?,
+        }
+        write!(f, " references an unknown type `{}`", self.name)
+    }
+}
+
+/// Check that every type referenced by a field, method, operator or alias is either a lua primitive or one of the `types`
+///
+/// The parameter and return types of a `fun(..)` are checked too
+///
+/// A referenced type that is only generated in another file should be added to `types`,
+/// e.g. with a [`Registry`](crate::Registry)
+///
+/// It also checks that no class has the same field twice, which the derive can't see when it comes from an `inline` field
+///
+/// The errors are in the order of the `types`, e.g. ``"`Widget.owner` references an unknown type `Usr`"``,
+/// followed by the duplicate fields, e.g. ``"`Widget.posX` is a duplicate field"``.
+/// See [`validate_types`] for the structured errors
+pub fn validate_all(types: &[Type]) -> Result<(), Vec<String>> {
+    let mut errors = match validate_types(types, &[]) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|err| format!("{err}")).collect(),
+    };
+
+    for ty in types {
+        let Type::Class(class) = ty else { continue };
+        let mut fields = Vec::new();
+        inline_fields(&mut fields, class.fields);
+
+        let mut seen = BTreeSet::new();
+        for field in fields {
+            if !seen.insert((field.index || field.positional, field.name.trim())) {
+                errors.push(format!(
+                    "`{}.{}` is a duplicate field",
+                    class.name.trim(),
+                    field.name.trim()
+                ));
+            }
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+/// Like [`validate_all`], but the `allowed` names are also known and the errors are structured
+///
+/// `allowed` is for types that are declared some other way, e.g. `vec3` from a hand-written stub file
+pub fn validate_types(types: &[Type], allowed: &[&str]) -> Result<(), Vec<UnknownType>> {
+    let mut known = allowed
+        .iter()
+        .map(|name| name.trim())
+        .collect::<BTreeSet<_>>();
+    for ty in types {
+        known.insert(ty.name().trim());
+        if let Type::Enum(enum_) = ty {
+            if let ValueAlias::Named(name) = enum_.value_alias {
+                known.insert(name.trim());
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    for ty in types {
+        match ty {
+            Type::Class(class) => {
+                // an inlined field's type is replaced by the fields of its class
+                let mut fields = Vec::new();
+                inline_fields(&mut fields, class.fields);
+
+                for field in fields {
+                    let mut unknown = Vec::new();
+                    if field.index {
+                        check(
+                            &LuaType::parse(field.name),
+                            &known,
+                            class.generics,
+                            &mut unknown,
+                        );
+                    }
+                    check(
+                        &LuaType::parse(field.ty),
+                        &known,
+                        class.generics,
+                        &mut unknown,
+                    );
+                    errors.extend(unknown.into_iter().map(|name| UnknownType {
+                        ty: class.name.trim(),
+                        field: Some(field.name.trim()),
+                        name,
+                    }));
+                }
+
+                for method in class.methods {
+                    let mut unknown = Vec::new();
+                    check(
+                        &LuaType::parse(method.ty),
+                        &known,
+                        class.generics,
+                        &mut unknown,
+                    );
+                    errors.extend(unknown.into_iter().map(|name| UnknownType {
+                        ty: class.name.trim(),
+                        field: Some(method.name.trim()),
+                        name,
+                    }));
+                }
+
+                for operator in class.operators {
+                    let mut unknown = Vec::new();
+                    for ty in operator.rhs.into_iter().chain([operator.result]) {
+                        check(&LuaType::parse(ty), &known, class.generics, &mut unknown);
+                    }
+                    errors.extend(unknown.into_iter().map(|name| UnknownType {
+                        ty: class.name.trim(),
+                        field: Some(operator.name.trim()),
+                        name,
+                    }));
+                }
+            }
+            Type::Alias(alias) => {
+                let mut unknown = Vec::new();
+                check(&LuaType::parse(alias.ty), &known, &[], &mut unknown);
+                errors.extend(unknown.into_iter().map(|name| UnknownType {
+                    ty: alias.name.trim(),
+                    field: None,
+                    name,
+                }));
+            }
+            Type::Enum(..) | Type::Builtin(..) => {}
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+fn check(ty: &LuaType, known: &BTreeSet<&str>, generics: &[&str], unknown: &mut Vec<String>) {
+    match ty {
+        LuaType::Name(name) => check_name(name, known, generics, unknown),
+        LuaType::Optional(ty) | LuaType::Array(ty) => check(ty, known, generics, unknown),
+        LuaType::Table(key, value) => {
+            check(key, known, generics, unknown);
+            check(value, known, generics, unknown);
+        }
+        LuaType::Tuple(types) | LuaType::Union(types) => types
+            .iter()
+            .for_each(|ty| check(ty, known, generics, unknown)),
+    }
+}
+
+fn check_name(name: &str, known: &BTreeSet<&str>, generics: &[&str], unknown: &mut Vec<String>) {
+    let name = name.trim();
+
+    if let Some(signature) = name.strip_prefix("fun") {
+        check_function(signature, known, generics, unknown);
+        return;
+    }
+
+    // literals and table shapes aren't checked
+    let literal = name.starts_with(['"', '\'', '`', '-', '{'])
+        || name.starts_with(|c: char| c.is_ascii_digit());
+    if literal || PRIMITIVES.contains(&name) || known.contains(name) || generics.contains(&name) {
+        return;
+    }
+
+    // a generic type, e.g. `Container<string>`
+    if let Some((base, args)) = name.strip_suffix('>').and_then(|name| name.split_once('<')) {
+        check_name(base, known, generics, unknown);
+        for arg in split_top_level(args, ',') {
+            check(&LuaType::parse(arg), known, generics, unknown);
+        }
+        return;
+    }
+
+    unknown.push(name.into());
+}
+
+// the parameter and return types of a `fun(name: A, ...): R`, untyped parameters like `self` aren't checked
+fn check_function(
+    signature: &str,
+    known: &BTreeSet<&str>,
+    generics: &[&str],
+    unknown: &mut Vec<String>,
+) {
+    let Some(params) = signature.trim_start().strip_prefix('(') else {
+        return;
+    };
+
+    let mut depth = 0_usize;
+    let Some(end) = params.find(|ch| match ch {
+        '(' | '[' | '<' | '{' => {
+            depth += 1;
+            false
+        }
+        ')' if depth == 0 => true,
+        ')' | ']' | '>' | '}' => {
+            depth = depth.saturating_sub(1);
+            false
+        }
+        _ => false,
+    }) else {
+        return;
+    };
+
+    let (params, returns) = (&params[..end], &params[end + 1..]);
+    for param in split_top_level(params, ',') {
+        if let Some((_, ty)) = param.split_once(':') {
+            check(&LuaType::parse(ty), known, generics, unknown);
+        }
+    }
+
+    if let Some(returns) = returns.trim_start().strip_prefix(':') {
+        // a return can be named, e.g. `ok: boolean`
+        for ret in split_top_level(returns, ',') {
+            let ty = ret.split_once(':').map_or(ret, |(_, ty)| ty);
+            check(&LuaType::parse(ty), known, generics, unknown);
+        }
+    }
+}